@@ -0,0 +1,414 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Rasterizes the bundled OpenType font into `MonoFont`s, `AntiAliasedFont`s and
+//! `ProportionalFont`s at compile time
+//!
+//! Each entry of the `GENERATED_*` tables results in a `pub const` with the given name in
+//! `$OUT_DIR/fonts.rs`, which is included by `src/font.rs`. Additional sizes of the
+//! `OVERPASS_DIGITS_*` fonts are generated for the comma separated pixel heights in the
+//! `WATCHFACE_FONT_SIZES` environment variable.
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const FONT_FILE: &str = "data/overpass-mono-bold.otf";
+
+// Defines `DIGIT_CHARACTERS`, the characters in the generated fonts
+include!("src/font_characters.rs");
+
+/// Generated fonts as `(name, pixel height, characters)`
+const GENERATED_FONTS: &[(&str, u32, &str)] = &[
+    ("OVERPASS_DIGITS_24", 24, DIGIT_CHARACTERS),
+    ("OVERPASS_DIGITS_40", 40, DIGIT_CHARACTERS),
+    ("OVERPASS_DIGITS_72", 72, DIGIT_CHARACTERS),
+];
+
+/// Environment variable with additional pixel heights of `OVERPASS_DIGITS_*` fonts, like `16,32`
+const FONT_SIZES_VARIABLE: &str = "WATCHFACE_FONT_SIZES";
+
+/// Generated anti-aliased fonts as `(name, pixel height, characters, bits per pixel)`
+const GENERATED_ANTI_ALIASED_FONTS: &[(&str, u32, &str, u8)] =
+    &[("OVERPASS_DIGITS_AA_64", 64, DIGIT_CHARACTERS, 4)];
+
+/// Generated proportional fonts as `(name, pixel height, characters)`
+const GENERATED_PROPORTIONAL_FONTS: &[(&str, u32, &str)] =
+    &[("OVERPASS_DIGITS_PROPORTIONAL_64", 64, DIGIT_CHARACTERS)];
+
+/// Characters for which the kerning is calculated in proportional fonts
+const KERNING_CHARACTERS: &str = "0123456789";
+
+/// Glyphs are rasterized to one bit per pixel, pixels with more coverage are set
+const COVERAGE_THRESHOLD: f32 = 0.5;
+
+/// Size and position of the glyphs of a font at a certain pixel height
+struct CellMetrics {
+    width: u32,
+    height: u32,
+    baseline: u32,
+}
+
+impl CellMetrics {
+    fn new<F: Font>(font: &F, pixel_height: u32, characters: &str) -> Self {
+        let scaled = font.as_scaled(PxScale::from(pixel_height as f32));
+        let width = characters
+            .chars()
+            .map(|c| scaled.h_advance(font.glyph_id(c)))
+            .fold(0.0, f32::max);
+
+        CellMetrics {
+            width: width.ceil() as u32,
+            height: (scaled.ascent() - scaled.descent()).ceil() as u32,
+            baseline: scaled.ascent().ceil() as u32,
+        }
+    }
+}
+
+/// Rasterize all characters into a single row of glyphs, with the coverage (0.0 - 1.0) of each
+/// pixel
+fn rasterize<F: Font>(
+    font: &F,
+    pixel_height: u32,
+    characters: &str,
+    metrics: &CellMetrics,
+) -> Vec<Vec<f32>> {
+    let scale = PxScale::from(pixel_height as f32);
+    let scaled = font.as_scaled(scale);
+    let image_width = metrics.width * characters.chars().count() as u32;
+    let mut image = vec![vec![0.0; image_width as usize]; metrics.height as usize];
+
+    for (index, c) in characters.chars().enumerate() {
+        let glyph_id = font.glyph_id(c);
+        let cell_x = (index as u32 * metrics.width) as f32;
+        let x = cell_x + (metrics.width as f32 - scaled.h_advance(glyph_id)) / 2.0;
+        let glyph =
+            glyph_id.with_scale_and_position(scale, ab_glyph::point(x, metrics.baseline as f32));
+
+        if let Some(outline) = font.outline_glyph(glyph) {
+            let bounds = outline.px_bounds();
+            outline.draw(|x, y, coverage| {
+                let x = x as i32 + bounds.min.x as i32;
+                let y = y as i32 + bounds.min.y as i32;
+                let in_cell = x >= cell_x as i32 && x < (cell_x as u32 + metrics.width) as i32;
+                if in_cell && y >= 0 && y < metrics.height as i32 {
+                    image[y as usize][x as usize] = coverage;
+                }
+            });
+        }
+    }
+
+    image
+}
+
+/// Pack an image to one bit per pixel, with each row padded to a whole byte
+fn pack_binary(image: &[Vec<f32>]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for row in image {
+        for chunk in row.chunks(8) {
+            let mut byte = 0u8;
+            for (bit, coverage) in chunk.iter().enumerate() {
+                if *coverage > COVERAGE_THRESHOLD {
+                    byte |= 0x80 >> bit;
+                }
+            }
+            data.push(byte);
+        }
+    }
+    data
+}
+
+/// Pack an image to the given number of bits of alpha per pixel, with each row padded to a whole
+/// byte
+fn pack_alpha(image: &[Vec<f32>], bits_per_pixel: u8) -> Vec<u8> {
+    let pixels_per_byte = (8 / bits_per_pixel) as usize;
+    let max = ((1u32 << bits_per_pixel) - 1) as f32;
+
+    let mut data = Vec::new();
+    for row in image {
+        for chunk in row.chunks(pixels_per_byte) {
+            let mut byte = 0u8;
+            for (index, coverage) in chunk.iter().enumerate() {
+                let value = (coverage.clamp(0.0, 1.0) * max).round() as u8;
+                byte |= value << (8 - bits_per_pixel as usize * (index + 1));
+            }
+            data.push(byte);
+        }
+    }
+    data
+}
+
+/// Horizontal ink bounds of a glyph in a rasterized image, as `(first column, last column)`
+fn ink_bounds(image: &[Vec<f32>], cell_x: usize, cell_width: usize) -> Option<(usize, usize)> {
+    let has_ink = |x: usize| image.iter().any(|row| row[x] > COVERAGE_THRESHOLD);
+    let first = (cell_x..cell_x + cell_width).find(|x| has_ink(*x))?;
+    let last = (cell_x..cell_x + cell_width).rev().find(|x| has_ink(*x))?;
+    Some((first, last))
+}
+
+/// Empty space on the left and right of a glyph in each row, `None` for rows without ink
+fn row_profiles(image: &[Vec<f32>], first: usize, last: usize) -> Vec<Option<(usize, usize)>> {
+    image
+        .iter()
+        .map(|row| {
+            let left = (first..=last).find(|x| row[*x] > COVERAGE_THRESHOLD)?;
+            let right = (first..=last)
+                .rev()
+                .find(|x| row[*x] > COVERAGE_THRESHOLD)?;
+            Some((left - first, last - right))
+        })
+        .collect()
+}
+
+/// Negative adjustment that brings two glyphs as close as the normal spacing allows
+fn kerning(
+    left: &[Option<(usize, usize)>],
+    right: &[Option<(usize, usize)>],
+    max_adjustment: usize,
+) -> i32 {
+    let closest = left
+        .iter()
+        .zip(right)
+        .filter_map(|(left, right)| match (left, right) {
+            (Some((_, left_space)), Some((right_space, _))) => Some(left_space + right_space),
+            _ => None,
+        })
+        .min()
+        .unwrap_or(0);
+
+    -(closest.min(max_adjustment) as i32)
+}
+
+/// Fonts of `GENERATED_FONTS` plus the sizes requested in `FONT_SIZES_VARIABLE`
+fn generated_fonts() -> Vec<(String, u32, &'static str)> {
+    let mut fonts: Vec<_> = GENERATED_FONTS
+        .iter()
+        .map(|(name, pixel_height, characters)| (name.to_string(), *pixel_height, *characters))
+        .collect();
+
+    let sizes = env::var(FONT_SIZES_VARIABLE).unwrap_or_default();
+    for size in sizes
+        .split(',')
+        .map(str::trim)
+        .filter(|size| !size.is_empty())
+    {
+        let pixel_height = match size.parse::<u32>() {
+            Ok(pixel_height) if (8..=256).contains(&pixel_height) => pixel_height,
+            _ => panic!(
+                "{} contains {:?}, expected pixel heights from 8 to 256",
+                FONT_SIZES_VARIABLE, size
+            ),
+        };
+        if fonts.iter().all(|(_, height, _)| *height != pixel_height) {
+            let name = format!("OVERPASS_DIGITS_{}", pixel_height);
+            fonts.push((name, pixel_height, DIGIT_CHARACTERS));
+        }
+    }
+
+    fonts
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", FONT_FILE);
+    println!("cargo:rerun-if-changed=src/font_characters.rs");
+    println!("cargo:rerun-if-env-changed={}", FONT_SIZES_VARIABLE);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let font_data = fs::read(FONT_FILE).unwrap();
+    let font = FontRef::try_from_slice(&font_data).unwrap();
+
+    let mut code = String::new();
+    for (name, pixel_height, characters) in generated_fonts() {
+        let metrics = CellMetrics::new(&font, pixel_height, characters);
+        let image = rasterize(&font, pixel_height, characters, &metrics);
+
+        let file_name = format!("{}.raw", name.to_lowercase());
+        fs::write(Path::new(&out_dir).join(&file_name), pack_binary(&image)).unwrap();
+
+        let character_count = characters.chars().count();
+        writeln!(
+            code,
+            "/// Overpass Mono Bold rasterized at {height} pixels, containing `{characters}`
+pub const {name}: MonoFont = MonoFont {{
+    image: ImageRaw::new_binary(
+        include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{file_name}\")),
+        {image_width},
+    ),
+    glyph_mapping: &StrGlyphMapping::new({characters:?}, {replacement}),
+    character_size: Size::new({width}, {cell_height}),
+    character_spacing: 0,
+    baseline: {baseline},
+    underline: DecorationDimensions::new({underline}, 1),
+    strikethrough: DecorationDimensions::new({strikethrough}, 1),
+}};
+",
+            height = pixel_height,
+            characters = characters,
+            name = name,
+            file_name = file_name,
+            image_width = metrics.width * character_count as u32,
+            replacement = character_count - 1,
+            width = metrics.width,
+            cell_height = metrics.height,
+            baseline = metrics.baseline,
+            underline = metrics.baseline + 1,
+            strikethrough = metrics.height / 2,
+        )
+        .unwrap();
+    }
+
+    for (name, pixel_height, characters, bits_per_pixel) in GENERATED_ANTI_ALIASED_FONTS {
+        let metrics = CellMetrics::new(&font, *pixel_height, characters);
+        let image = rasterize(&font, *pixel_height, characters, &metrics);
+
+        let file_name = format!("{}.raw", name.to_lowercase());
+        fs::write(
+            Path::new(&out_dir).join(&file_name),
+            pack_alpha(&image, *bits_per_pixel),
+        )
+        .unwrap();
+
+        let character_count = characters.chars().count();
+        writeln!(
+            code,
+            "/// Overpass Mono Bold rasterized at {height} pixels with {bits} bits of alpha, containing
+/// `{characters}`
+pub const {name}: AntiAliasedFont = AntiAliasedFont {{
+    data: include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{file_name}\")),
+    bits_per_pixel: BitsPerPixel::{depth},
+    image_width: {image_width},
+    glyph_mapping: &StrGlyphMapping::new({characters:?}, {replacement}),
+    character_size: Size::new({width}, {cell_height}),
+    character_spacing: 0,
+    baseline: {baseline},
+}};
+",
+            height = pixel_height,
+            bits = bits_per_pixel,
+            depth = match bits_per_pixel {
+                1 => "One",
+                2 => "Two",
+                4 => "Four",
+                8 => "Eight",
+                _ => panic!("unsupported bits per pixel: {}", bits_per_pixel),
+            },
+            characters = characters,
+            name = name,
+            file_name = file_name,
+            image_width = metrics.width * character_count as u32,
+            replacement = character_count - 1,
+            width = metrics.width,
+            cell_height = metrics.height,
+            baseline = metrics.baseline,
+        )
+        .unwrap();
+    }
+
+    for (name, pixel_height, characters) in GENERATED_PROPORTIONAL_FONTS {
+        let metrics = CellMetrics::new(&font, *pixel_height, characters);
+        let image = rasterize(&font, *pixel_height, characters, &metrics);
+        let spacing = (pixel_height + 8) / 16;
+
+        // Crop each glyph to its ink, glyphs without ink (like space) keep half their cell
+        let mut columns = Vec::new();
+        let mut glyphs = Vec::new();
+        let mut profiles = Vec::new();
+        for (index, c) in characters.chars().enumerate() {
+            let cell_x = index * metrics.width as usize;
+            let offset = columns.len();
+            match ink_bounds(&image, cell_x, metrics.width as usize) {
+                Some((first, last)) => {
+                    columns.extend(first..=last);
+                    let width = last - first + 1;
+                    glyphs.push((c, offset, width, width + spacing as usize));
+                    profiles.push(row_profiles(&image, first, last));
+                }
+                None => {
+                    let width = metrics.width as usize / 2;
+                    glyphs.push((c, offset, 0, width));
+                    profiles.push(vec![None; image.len()]);
+                }
+            }
+        }
+
+        let cropped: Vec<Vec<f32>> = image
+            .iter()
+            .map(|row| columns.iter().map(|x| row[*x]).collect())
+            .collect();
+
+        let file_name = format!("{}.raw", name.to_lowercase());
+        fs::write(Path::new(&out_dir).join(&file_name), pack_binary(&cropped)).unwrap();
+
+        let glyph_code = |(c, offset, width, advance): &(char, usize, usize, usize)| {
+            format!(
+                "ProportionalGlyph {{ character: {:?}, offset: {}, width: {}, advance: {} }}",
+                c, offset, width, advance
+            )
+        };
+
+        let mut kerning_code = String::new();
+        let max_adjustment = (metrics.width / 4) as usize;
+        for (left_index, left) in characters.chars().enumerate() {
+            for (right_index, right) in characters.chars().enumerate() {
+                if !KERNING_CHARACTERS.contains(left) || !KERNING_CHARACTERS.contains(right) {
+                    continue;
+                }
+                let adjustment = kerning(
+                    &profiles[left_index],
+                    &profiles[right_index],
+                    max_adjustment,
+                );
+                if adjustment != 0 {
+                    write!(kerning_code, "({:?}, {:?}, {}), ", left, right, adjustment).unwrap();
+                }
+            }
+        }
+
+        let (replacement, glyphs) = glyphs.split_last().unwrap();
+        writeln!(
+            code,
+            "/// Overpass Mono Bold rasterized at {height} pixels with proportional widths, containing
+/// `{characters}`
+pub const {name}: ProportionalFont = ProportionalFont {{
+    data: include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{file_name}\")),
+    image_width: {image_width},
+    height: {cell_height},
+    baseline: {baseline},
+    glyphs: &[{glyphs}],
+    replacement: {replacement},
+    kerning: &[{kerning}],
+}};
+",
+            height = pixel_height,
+            characters = characters,
+            name = name,
+            file_name = file_name,
+            image_width = columns.len(),
+            cell_height = metrics.height,
+            baseline = metrics.baseline,
+            glyphs = glyphs.iter().map(glyph_code).collect::<Vec<_>>().join(", "),
+            replacement = glyph_code(replacement),
+            kerning = kerning_code,
+        )
+        .unwrap();
+    }
+
+    fs::write(Path::new(&out_dir).join("fonts.rs"), code).unwrap();
+}
@@ -15,51 +15,281 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
-use chrono::{Local, Timelike};
+//! Interactive simulator
+//!
+//! Plays a scenario script and allows changing the watchface data and style with the keyboard
+//! and mouse. Run with `--help` for the command line options.
 
+use chrono::Local;
+use core::time::Duration;
+use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder};
 use embedded_graphics::pixelcolor::Rgb565;
 use embedded_graphics::prelude::*;
+use embedded_graphics::text::{Baseline, Text};
+use embedded_graphics_simulator::sdl2::{Keycode, Mod, MouseButton};
 use embedded_graphics_simulator::{
     OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
 };
+use std::fs;
+use std::process::exit;
 use std::thread;
-use std::time::Duration;
+use std::time::Instant;
 use watchface::battery::{ChargerState, StateOfCharge};
-use watchface::{SimpleWatchfaceStyle, Watchface};
+use watchface::scenario::{Scenario, ScenarioPlayer};
+use watchface::time::Time;
+use watchface::{NamedStyle, StyleName, Watchface};
+
+const USAGE: &str = "Usage: simulator [OPTIONS]
+
+Options:
+  --scenario <FILE>    Scenario script to play (default: examples/scenarios/battery-cycle.scenario)
+  --speed <FACTOR>     Playback speed, 60 plays a minute per second (default: 1)
+  --start <HH:MM:SS>   Time at which the scenario starts (default: from the script or now)
+
+Controls:
+  Left / Right         Step the time a minute back or forward, an hour with Shift
+  Mouse wheel          Step the time five minutes back or forward
+  Up / Down            Increase or decrease the battery level
+  C / right click      Cycle the charger state
+  S / left click       Cycle the watchface style
+  O                    Toggle the overlay
+  R                    Reset to the scenario data";
+
+const DEFAULT_SCENARIO: &str = include_str!("scenarios/battery-cycle.scenario");
+
+const CHARGER_STATES: [ChargerState; 7] = [
+    ChargerState::Discharging,
+    ChargerState::Charging,
+    ChargerState::Full,
+    ChargerState::Paused,
+    ChargerState::OverTemperature,
+    ChargerState::Fault,
+    ChargerState::Unknown,
+];
+
+/// Returns the style after the given one, wrapping around to the first
+fn next_style(style: StyleName) -> StyleName {
+    let index = StyleName::ALL.iter().position(|s| *s == style).unwrap();
+    StyleName::ALL[(index + 1) % StyleName::ALL.len()]
+}
+
+/// Changes made by the user on top of the scenario data
+struct Controls {
+    style: StyleName,
+    overlay: bool,
+    time_offset: i64,
+    battery: Option<u8>,
+    charger: Option<ChargerState>,
+}
+
+impl Controls {
+    fn new() -> Self {
+        Self {
+            style: StyleName::Simple,
+            overlay: true,
+            time_offset: 0,
+            battery: None,
+            charger: None,
+        }
+    }
+
+    fn apply(&self, mut watchface: Watchface) -> Watchface {
+        let offset = Duration::from_secs(self.time_offset.unsigned_abs());
+        watchface.time = watchface.time.map(|time| {
+            if self.time_offset >= 0 {
+                time + offset
+            } else {
+                time - offset
+            }
+        });
+        if let Some(percentage) = self.battery {
+            watchface.battery = Some(StateOfCharge::from_percentage(percentage));
+        }
+        if let Some(charger) = self.charger {
+            watchface.charger = Some(charger);
+        }
+        watchface
+    }
+
+    fn step_battery(&mut self, current: Option<StateOfCharge>, up: bool) {
+        let percentage = self
+            .battery
+            .or_else(|| current.map(|battery| (battery.percentage() + 2) / 5 * 5))
+            .unwrap_or(50);
+        self.battery = Some(if up {
+            (percentage + 5).min(100)
+        } else {
+            percentage.saturating_sub(5)
+        });
+    }
+
+    fn cycle_charger(&mut self, current: Option<ChargerState>) {
+        let index = self
+            .charger
+            .or(current)
+            .and_then(|charger| CHARGER_STATES.iter().position(|&state| state == charger))
+            .map_or(0, |index| (index + 1) % CHARGER_STATES.len());
+        self.charger = Some(CHARGER_STATES[index]);
+    }
+
+    /// Handle an event, returns false when the simulator should quit
+    fn handle(&mut self, event: SimulatorEvent, watchface: &Watchface) -> bool {
+        match event {
+            SimulatorEvent::Quit => return false,
+            SimulatorEvent::KeyDown {
+                keycode, keymod, ..
+            } => {
+                let step = if keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) {
+                    60 * 60
+                } else {
+                    60
+                };
+                match keycode {
+                    Keycode::Left => self.time_offset -= step,
+                    Keycode::Right => self.time_offset += step,
+                    Keycode::Up => self.step_battery(watchface.battery, true),
+                    Keycode::Down => self.step_battery(watchface.battery, false),
+                    Keycode::C => self.cycle_charger(watchface.charger),
+                    Keycode::S => self.style = next_style(self.style),
+                    Keycode::O => self.overlay = !self.overlay,
+                    Keycode::R => {
+                        *self = Self {
+                            style: self.style,
+                            overlay: self.overlay,
+                            ..Self::new()
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            SimulatorEvent::MouseButtonUp { mouse_btn, .. } => match mouse_btn {
+                MouseButton::Left => self.style = next_style(self.style),
+                MouseButton::Right => self.cycle_charger(watchface.charger),
+                _ => {}
+            },
+            SimulatorEvent::MouseWheel { scroll_delta, .. } => {
+                self.time_offset += scroll_delta.y.signum() as i64 * 5 * 60;
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Draw the watchface data and style at the bottom of the display
+fn draw_overlay<D>(display: &mut D, watchface: &Watchface, style: StyleName) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Rgb565>,
+{
+    let time = match watchface.time {
+        Some(time) => time.to_string(),
+        None => String::from("-"),
+    };
+    let battery = match watchface.battery {
+        Some(battery) => format!("{}%", battery.percentage()),
+        None => String::from("-"),
+    };
+    let charger = match watchface.charger {
+        Some(charger) => format!("{:?}", charger),
+        None => String::from("-"),
+    };
+    let notifications = match watchface.notifications {
+        Some(count) => count.to_string(),
+        None => String::from("-"),
+    };
+    let text = format!(
+        "time {}  battery {}\ncharger {}  notifications {}\nstyle {}",
+        time, battery, charger, notifications, style
+    );
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(&FONT_6X10)
+        .text_color(Rgb565::YELLOW)
+        .background_color(Rgb565::BLACK)
+        .build();
+    let position = Point::new(2, display.bounding_box().size.height as i32 - 32);
+    Text::with_baseline(&text, position, text_style, Baseline::Top).draw(display)?;
+
+    Ok(())
+}
+
+fn parse_args() -> Result<Option<ScenarioPlayer>, String> {
+    let mut script = String::from(DEFAULT_SCENARIO);
+    let mut speed = 1.0;
+    let mut start: Option<Time> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(option) = args.next() {
+        if option == "--help" {
+            return Ok(None);
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", option))?;
+        let invalid = || format!("Invalid value for {}: {}", option, value);
+        match option.as_str() {
+            "--scenario" => {
+                script = fs::read_to_string(&value).map_err(|e| format!("{}: {}", value, e))?
+            }
+            "--speed" => {
+                speed = value
+                    .parse()
+                    .ok()
+                    .filter(|speed: &f32| speed.is_finite() && *speed > 0.0)
+                    .ok_or_else(invalid)?
+            }
+            "--start" => start = Some(value.parse().map_err(|_| invalid())?),
+            _ => return Err(format!("Unknown option: {}", option)),
+        }
+    }
+
+    let mut scenario = Scenario::parse(&script).map_err(|e| format!("Scenario {}", e))?;
+    if let Some(start) = start {
+        scenario = scenario.with_start(start);
+    } else if scenario.start().is_none() {
+        scenario = scenario.with_start(Local::now().into());
+    }
+
+    Ok(Some(ScenarioPlayer::new(scenario, speed)))
+}
 
 fn main() -> Result<(), core::convert::Infallible> {
+    let player = match parse_args() {
+        Ok(Some(player)) => player,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            exit(1);
+        }
+    };
+
     let mut display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(240, 240));
 
     let output_settings = OutputSettingsBuilder::new().build();
-    let mut window = Window::new("Simple Watchface", &output_settings);
+    let mut window = Window::new("Watchface", &output_settings);
 
-    'running: loop {
-        let seconds = Local::now().second() as u8;
-        let (battery_percentage, charger_state) = if seconds < 25 {
-            (100 - (seconds * 4), ChargerState::Discharging)
-        } else if seconds < 30 {
-            (0, ChargerState::Discharging)
-        } else if seconds < 55 {
-            (100 - (55 - seconds) * 4, ChargerState::Charging)
-        } else {
-            (100, ChargerState::Full)
-        };
-
-        let style = SimpleWatchfaceStyle::default();
+    let mut controls = Controls::new();
+    let started = Instant::now();
 
-        let watchface = Watchface::build()
-            .with_time(Local::now())
-            .with_battery(StateOfCharge::from_percentage(battery_percentage))
-            .with_charger(charger_state)
-            .into_styled(style);
+    'running: loop {
+        let mut watchface = controls.apply(player.watchface());
+        watchface.animation_time = Some(started.elapsed().as_millis() as u32);
 
-        watchface.draw(&mut display)?;
+        watchface
+            .clone()
+            .into_styled(NamedStyle::new(controls.style))
+            .draw(&mut display)?;
+        if controls.overlay {
+            draw_overlay(&mut display, &watchface, controls.style)?;
+        }
         window.update(&display);
 
         for event in window.events() {
-            match event {
-                SimulatorEvent::Quit => break 'running,
-                _ => {}
+            if !controls.handle(event, &watchface) {
+                break 'running;
             }
         }
         thread::sleep(Duration::from_millis(200));
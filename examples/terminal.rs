@@ -0,0 +1,61 @@
+/* Copyright (C) 2020 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Show the simple watchface in a terminal, for example over SSH
+//!
+//! Run with an optional scale factor, the default scales 240x240 down to 120x60 characters:
+//! ```bash
+//! cargo run --example terminal -- 2
+//! ```
+
+use chrono::Local;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+use watchface::battery::{ChargerState, StateOfCharge};
+use watchface::terminal::TerminalDisplay;
+use watchface::{SimpleWatchfaceStyle, Watchface};
+
+fn main() -> io::Result<()> {
+    let scale = match std::env::args().nth(1) {
+        Some(scale) => scale.parse().expect("Scale should be a positive number"),
+        None => 2,
+    };
+
+    let mut display = TerminalDisplay::<Rgb888>::new(Size::new(240, 240)).with_scale(scale);
+    let mut stdout = io::stdout();
+
+    // Clear the screen once, then redraw in place
+    write!(stdout, "\x1b[2J")?;
+    loop {
+        Watchface::build()
+            .with_time(Local::now())
+            .with_battery(StateOfCharge::from_percentage(80))
+            .with_charger(ChargerState::Discharging)
+            .into_styled(SimpleWatchfaceStyle::default())
+            .draw(&mut display)
+            .unwrap();
+
+        write!(stdout, "\x1b[H")?;
+        display.write_to(&mut stdout)?;
+        stdout.flush()?;
+
+        thread::sleep(Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,362 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Anti-aliased text rendering
+//!
+//! `MonoFont` stores one bit per pixel, which results in jagged edges for large text. An
+//! `AntiAliasedFont` stores an alpha value per pixel, which is blended between the text color and
+//! the background color while drawing. With `DrawOnBackground::draw_on_background` the text is
+//! blended with the pixels of a `Background` instead.
+
+use crate::background::{Background, DrawOnBackground, PlainBackground};
+use embedded_graphics::mono_font::mapping::GlyphMapping;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Number of bits of alpha per pixel in an `AntiAliasedFont`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BitsPerPixel {
+    /// Every pixel is either transparent or opaque
+    One,
+    /// 4 levels of alpha
+    Two,
+    /// 16 levels of alpha
+    Four,
+    /// 256 levels of alpha
+    Eight,
+}
+
+impl BitsPerPixel {
+    /// Returns the number of bits
+    pub const fn bits(self) -> u32 {
+        match self {
+            BitsPerPixel::One => 1,
+            BitsPerPixel::Two => 2,
+            BitsPerPixel::Four => 4,
+            BitsPerPixel::Eight => 8,
+        }
+    }
+}
+
+/// Monospaced font with 1, 2, 4 or 8 bits of alpha per pixel
+///
+/// The glyphs are stored next to each other in a single row. Each row of the image is padded to a
+/// whole byte and the leftmost pixel is stored in the most significant bits.
+pub struct AntiAliasedFont<'a> {
+    /// Alpha values of the glyphs
+    pub data: &'a [u8],
+    /// Number of bits of alpha per pixel
+    pub bits_per_pixel: BitsPerPixel,
+    /// Width of the image in pixels
+    pub image_width: u32,
+    /// Mapping from characters to glyph indices
+    pub glyph_mapping: &'a dyn GlyphMapping,
+    /// Size of a single glyph
+    pub character_size: Size,
+    /// Space between glyphs
+    pub character_spacing: u32,
+    /// Offset from the top of a glyph to the baseline
+    pub baseline: u32,
+}
+
+impl<'a> AntiAliasedFont<'a> {
+    /// Get the alpha (0..255) of a pixel of the glyph at the given index
+    fn alpha(&self, glyph_index: usize, point: Point) -> u8 {
+        let bits_per_pixel = self.bits_per_pixel.bits();
+        let pixels_per_byte = 8 / bits_per_pixel;
+        let bytes_per_row = self.image_width.div_ceil(pixels_per_byte);
+
+        let x = glyph_index as u32 * self.character_size.width + point.x as u32;
+        let byte = self.data[(point.y as u32 * bytes_per_row + x / pixels_per_byte) as usize];
+        let shift = 8 - bits_per_pixel * (x % pixels_per_byte + 1);
+        let max = (1u32 << bits_per_pixel) - 1;
+        let value = (byte as u32 >> shift) & max;
+
+        (value * 255 / max) as u8
+    }
+}
+
+/// Blend two colors, where alpha (0..255) is the amount of foreground
+fn blend<C>(foreground: C, background: C, alpha: u8) -> C
+where
+    C: RgbColor + From<Rgb888>,
+{
+    let channel = |foreground: u8, background: u8, max: u8| {
+        let foreground = foreground as u32 * 255 / max as u32;
+        let background = background as u32 * 255 / max as u32;
+        let alpha = alpha as u32;
+        ((foreground * alpha + background * (255 - alpha)) / 255) as u8
+    };
+
+    Rgb888::new(
+        channel(foreground.r(), background.r(), C::MAX_R),
+        channel(foreground.g(), background.g(), C::MAX_G),
+        channel(foreground.b(), background.b(), C::MAX_B),
+    )
+    .into()
+}
+
+/// A single line of anti-aliased text
+///
+/// The position is the top left corner of the text, so that it can be aligned using
+/// `embedded_layout`.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use embedded_layout::prelude::*;
+/// use watchface::anti_aliased_font::AntiAliasedText;
+/// use watchface::font::OVERPASS_DIGITS_AA_64;
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_out_of_bounds_drawing(true);
+///
+/// AntiAliasedText::new("8", Point::zero(), &OVERPASS_DIGITS_AA_64, Rgb888::WHITE, Rgb888::BLACK)
+///     .align_to(&display.bounding_box(), horizontal::Center, vertical::Center)
+///     .draw(&mut display)
+///     .unwrap();
+/// ```
+pub struct AntiAliasedText<'a, C> {
+    text: &'a str,
+    position: Point,
+    font: &'a AntiAliasedFont<'a>,
+    text_color: C,
+    background_color: C,
+}
+
+impl<'a, C> AntiAliasedText<'a, C> {
+    /// Create text which blends from `text_color` to `background_color` at the edges
+    pub fn new(
+        text: &'a str,
+        position: Point,
+        font: &'a AntiAliasedFont<'a>,
+        text_color: C,
+        background_color: C,
+    ) -> Self {
+        Self {
+            text,
+            position,
+            font,
+            text_color,
+            background_color,
+        }
+    }
+}
+
+impl<C> Drawable for AntiAliasedText<'_, C>
+where
+    C: RgbColor + From<Rgb888>,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+/// Blend the text with the background of the display, the background color is used where the
+/// background doesn't draw
+impl<C> DrawOnBackground for AntiAliasedText<'_, C>
+where
+    C: RgbColor + From<Rgb888>,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
+        let font = self.font;
+        let advance = (font.character_size.width + font.character_spacing) as i32;
+        let display_area = display.bounding_box();
+
+        for (index, c) in self.text.chars().enumerate() {
+            let glyph_position = self.position + Point::new(index as i32 * advance, 0);
+            let mut target = GlyphTarget {
+                display: &mut *display,
+                font,
+                glyph_index: font.glyph_mapping.index(c),
+                glyph_area: Rectangle::new(glyph_position, font.character_size),
+                text_color: self.text_color,
+            };
+
+            background.draw_background(&display_area, self.background_color, &mut target)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw target that turns the background pixels of a single glyph into blended text pixels
+///
+/// Pixels outside of the glyph and fully transparent pixels are not drawn.
+struct GlyphTarget<'a, D, C> {
+    display: &'a mut D,
+    font: &'a AntiAliasedFont<'a>,
+    glyph_index: usize,
+    glyph_area: Rectangle,
+    text_color: C,
+}
+
+impl<D, C> DrawTarget for GlyphTarget<'_, D, C>
+where
+    D: DrawTarget<Color = C>,
+    C: RgbColor + From<Rgb888>,
+{
+    type Color = C;
+
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let glyph_area = self.glyph_area;
+        let (font, glyph_index, text_color) = (self.font, self.glyph_index, self.text_color);
+
+        let pixels = pixels
+            .into_iter()
+            .filter(|Pixel(point, _)| glyph_area.contains(*point))
+            .filter_map(|Pixel(point, background)| {
+                let color = match font.alpha(glyph_index, point - glyph_area.top_left) {
+                    0 => return None,
+                    255 => text_color,
+                    alpha => blend(text_color, background, alpha),
+                };
+                Some(Pixel(point, color))
+            });
+
+        self.display.draw_iter(pixels)
+    }
+}
+
+impl<D, C> Dimensions for GlyphTarget<'_, D, C> {
+    fn bounding_box(&self) -> Rectangle {
+        self.glyph_area
+    }
+}
+
+impl<C> Transform for AntiAliasedText<'_, C>
+where
+    C: Copy,
+{
+    fn translate(&self, by: Point) -> Self {
+        Self {
+            position: self.position + by,
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.position += by;
+        self
+    }
+}
+
+impl<C> Dimensions for AntiAliasedText<'_, C> {
+    fn bounding_box(&self) -> Rectangle {
+        let font = self.font;
+        let count = self.text.chars().count() as u32;
+        let width = if count == 0 {
+            0
+        } else {
+            count * font.character_size.width + (count - 1) * font.character_spacing
+        };
+
+        Rectangle::new(self.position, Size::new(width, font.character_size.height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::mono_font::mapping::StrGlyphMapping;
+    use embedded_graphics::pixelcolor::Rgb565;
+
+    /// Two glyphs of 4x1 pixels: 'a' with increasing and 'b' with decreasing alpha
+    const TEST_FONT: AntiAliasedFont = AntiAliasedFont {
+        data: &[0b00_01_10_11, 0b11_10_01_00],
+        bits_per_pixel: BitsPerPixel::Two,
+        image_width: 8,
+        glyph_mapping: &StrGlyphMapping::new("ab", 0),
+        character_size: Size::new(4, 1),
+        character_spacing: 1,
+        baseline: 1,
+    };
+
+    #[test]
+    fn blend_colors() {
+        let text =
+            AntiAliasedText::new("ab", Point::zero(), &TEST_FONT, Rgb888::WHITE, Rgb888::BLUE);
+        let mut display = MockDisplay::new();
+        text.draw(&mut display).unwrap();
+
+        assert_eq!(display.get_pixel(Point::new(0, 0)), None);
+        assert_eq!(
+            display.get_pixel(Point::new(1, 0)),
+            Some(Rgb888::new(85, 85, 255))
+        );
+        assert_eq!(
+            display.get_pixel(Point::new(2, 0)),
+            Some(Rgb888::new(170, 170, 255))
+        );
+        assert_eq!(display.get_pixel(Point::new(3, 0)), Some(Rgb888::WHITE));
+        assert_eq!(display.get_pixel(Point::new(4, 0)), None);
+        assert_eq!(display.get_pixel(Point::new(5, 0)), Some(Rgb888::WHITE));
+        assert_eq!(display.get_pixel(Point::new(8, 0)), None);
+        assert_eq!(
+            text.bounding_box(),
+            Rectangle::new(Point::zero(), Size::new(9, 1))
+        );
+    }
+
+    #[test]
+    fn decode_bit_depths() {
+        let one_bit = AntiAliasedFont {
+            data: &[0b1010_0000],
+            bits_per_pixel: BitsPerPixel::One,
+            image_width: 4,
+            ..TEST_FONT
+        };
+        let alphas = [0, 1, 2, 3].map(|x| one_bit.alpha(0, Point::new(x, 0)));
+        assert_eq!(alphas, [255, 0, 255, 0]);
+
+        let eight_bit = AntiAliasedFont {
+            data: &[0, 1, 128, 255, 7, 6, 5, 4],
+            bits_per_pixel: BitsPerPixel::Eight,
+            ..TEST_FONT
+        };
+        let alphas = [0, 1, 2, 3].map(|x| eight_bit.alpha(0, Point::new(x, 0)));
+        assert_eq!(alphas, [0, 1, 128, 255]);
+        assert_eq!(eight_bit.alpha(1, Point::new(3, 0)), 4);
+    }
+
+    #[test]
+    fn blend_rgb565() {
+        let color = blend(Rgb565::WHITE, Rgb565::BLACK, 128);
+        assert_eq!(color, Rgb565::new(16, 32, 16));
+    }
+}
@@ -0,0 +1,76 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Backgrounds behind a watchface
+//!
+//! Every style starts by drawing its background, which is the background color of the style by
+//! default. `DrawOnBackground::draw_on_background` replaces that with a `Background`, for example
+//! the images of `decorated::Decorated`. Anti-aliased text is blended with the pixels of the
+//! background, so its edges match whatever is drawn behind it.
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Something that is drawn behind a watchface
+pub trait Background<C: PixelColor> {
+    /// Draw the background of the display area, `color` is the background color of the style
+    ///
+    /// The target may only accept a part of the display area, for example when anti-aliased text
+    /// blends with the background. The background must therefore be drawn relative to
+    /// `display_area` and not to the bounding box of the target.
+    fn draw_background<D>(
+        &self,
+        display_area: &Rectangle,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>;
+}
+
+/// The background color of the style, as drawn by `Drawable::draw`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PlainBackground;
+
+impl<C: PixelColor> Background<C> for PlainBackground {
+    fn draw_background<D>(
+        &self,
+        _display_area: &Rectangle,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        target.clear(color)
+    }
+}
+
+/// A drawable that can be drawn on top of a custom background
+///
+/// All styles implement this, their `Drawable::draw` draws on a `PlainBackground`.
+pub trait DrawOnBackground: Drawable {
+    /// Draw on top of the background, instead of clearing the display with the background color
+    fn draw_on_background<D, B>(
+        &self,
+        display: &mut D,
+        background: &B,
+    ) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+        B: Background<Self::Color>;
+}
@@ -15,8 +15,12 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+use core::str::FromStr;
+
 /// Indicated the state of the charger of a battery
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ChargerState {
     /// No power source is connected, therefore the battery is discharging
     Discharging,
@@ -24,21 +28,60 @@ pub enum ChargerState {
     Charging,
     /// A power source is connected, but the battery is fully charged
     Full,
+    /// A power source is connected, but charging is temporarily suspended
+    Paused,
+    /// Charging is stopped, because the battery temperature is out of range
+    OverTemperature,
+    /// The charger or battery reported a fault
+    Fault,
+    /// The state of the charger could not be determined
+    Unknown,
+}
+
+/// Error returned when parsing a `ChargerState` fails
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ParseChargerStateError;
+
+/// Parse a charger state from its snake case name
+///
+/// # Examples
+/// ```
+/// use watchface::battery::ChargerState;
+/// assert_eq!("charging".parse(), Ok(ChargerState::Charging));
+/// assert_eq!("over_temperature".parse(), Ok(ChargerState::OverTemperature));
+/// assert!("plugged".parse::<ChargerState>().is_err());
+/// ```
+impl FromStr for ChargerState {
+    type Err = ParseChargerStateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "discharging" => Ok(ChargerState::Discharging),
+            "charging" => Ok(ChargerState::Charging),
+            "full" => Ok(ChargerState::Full),
+            "paused" => Ok(ChargerState::Paused),
+            "over_temperature" => Ok(ChargerState::OverTemperature),
+            "fault" => Ok(ChargerState::Fault),
+            "unknown" => Ok(ChargerState::Unknown),
+            _ => Err(ParseChargerStateError),
+        }
+    }
 }
 
 /// Indicates a level of charge of a battery
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct StateOfCharge {
     level: u8,
 }
 
 impl StateOfCharge {
-    /// Create an instance from a percentage (0..100)
-    pub fn from_percentage(percentage: u8) -> Self {
-        // TODO: Make const
+    /// Create an instance from a percentage (0..100), rounded to the nearest level
+    pub const fn from_percentage(percentage: u8) -> Self {
         assert!(percentage <= 100);
         let percentage = percentage as u32;
-        let level = percentage * 255 / 100;
+        let level = (percentage * 255 + 50) / 100;
         Self::from_level(level as u8)
     }
 
@@ -52,10 +95,30 @@ impl StateOfCharge {
         self.level
     }
 
-    /// Get the percentage (0..100)
+    /// Get the percentage (0..100), rounded to the nearest percent
     pub fn percentage(&self) -> u8 {
         let level = self.level as u32;
-        let percentage = level * 100 / 255;
+        let percentage = (level * 100 + 127) / 255;
         percentage as u8
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentage_round_trip() {
+        for percentage in 0..=100 {
+            assert_eq!(
+                StateOfCharge::from_percentage(percentage).percentage(),
+                percentage
+            );
+        }
+        assert_eq!(StateOfCharge::from_level(0).percentage(), 0);
+        assert_eq!(StateOfCharge::from_level(1).percentage(), 0);
+        assert_eq!(StateOfCharge::from_level(2).percentage(), 1);
+        assert_eq!(StateOfCharge::from_level(254).percentage(), 100);
+        assert_eq!(StateOfCharge::from_level(255).percentage(), 100);
+    }
+}
@@ -18,29 +18,134 @@
 use crate::battery::{ChargerState, StateOfCharge};
 use core::marker::PhantomData;
 
+use core::fmt::Write;
+use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoFont, MonoTextStyle};
 use embedded_graphics::prelude::*;
-use embedded_graphics::primitives::{Polyline, PrimitiveStyleBuilder, Rectangle, Triangle};
+use embedded_graphics::primitives::{PrimitiveStyleBuilder, Rectangle, Triangle};
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use heapless::consts::*;
+use heapless::String;
 
-#[derive(Copy, Clone)]
+const BATTERY_WIDTH: u32 = 15;
+const BATTERY_HEIGHT: u32 = 25;
+const CHARGER_WIDTH: u32 = 15;
+const CHARGER_HEIGHT: u32 = 25;
+
+/// Outline of a vertical battery as `(x, y, width, height)` in unscaled pixels
+const BATTERY_OUTLINE: [(u32, u32, u32, u32); 8] = [
+    (3, 0, 9, 2),
+    (3, 2, 2, 3),
+    (10, 2, 2, 3),
+    (0, 5, 5, 2),
+    (10, 5, 5, 2),
+    (0, 7, 2, 16),
+    (13, 7, 2, 16),
+    (0, 23, 15, 2),
+];
+
+/// Inner area of a vertical battery which is filled in `BatteryFill::Continuous` mode
+const BATTERY_BODY: (u32, u32, u32, u32) = (3, 8, 9, 14);
+
+/// Width of the status symbols, which are drawn inside the battery outline
+const SYMBOL_WIDTH: u32 = 5;
+
+/// Bitmap of a status symbol, one byte per row with the leftmost pixel in bit 4
+type Symbol = [u8; 7];
+
+const QUESTION_MARK: Symbol = [
+    0b01110, 0b10001, 0b00001, 0b00110, 0b00100, 0b00000, 0b00100,
+];
+
+const EXCLAMATION_MARK: Symbol = [
+    0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100,
+];
+
+const THERMOMETER: Symbol = [
+    0b00100, 0b01010, 0b01010, 0b01110, 0b11111, 0b11111, 0b01110,
+];
+
+fn status_symbol(charger: ChargerState) -> Option<&'static Symbol> {
+    match charger {
+        ChargerState::Fault => Some(&EXCLAMATION_MARK),
+        ChargerState::OverTemperature => Some(&THERMOMETER),
+        ChargerState::Unknown => Some(&QUESTION_MARK),
+        _ => None,
+    }
+}
+
+/// Segments of a vertical battery, each lit when the state of charge is above its threshold
+const SEGMENTS: [(StateOfCharge, (u32, u32, u32, u32)); 4] = [
+    (StateOfCharge::from_percentage(10), (3, 18, 9, 4)),
+    (StateOfCharge::from_percentage(35), (3, 13, 9, 4)),
+    (StateOfCharge::from_percentage(65), (3, 8, 9, 4)),
+    (StateOfCharge::from_percentage(90), (6, 3, 3, 4)),
+];
+
+/// Largest factor accepted by `BatteryIconBuilder::with_scale`
+pub const MAX_SCALE: u32 = 16;
+
+/// Duration of a single frame of the charging animation
+const ANIMATION_FRAME_MS: u32 = 500;
+
+/// Number of characters reserved for the percentage label, enough for "100%"
+const LABEL_MAX_CHARACTERS: u32 = 4;
+/// Space between the battery and the percentage label in unscaled pixels
+const LABEL_SPACING: u32 = 2;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ChargerAlignment {
     Left,
+    #[default]
     Right,
 }
 
-impl ChargerAlignment {
-    fn battery_offset(&self) -> Point {
-        match self {
-            ChargerAlignment::Left => Point::new(15, 0),
-            ChargerAlignment::Right => Point::new(0, 0),
-        }
-    }
+/// Direction in which the battery is drawn
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BatteryOrientation {
+    /// The battery terminal points up
+    #[default]
+    Vertical,
+    /// The battery terminal points right
+    Horizontal,
+}
 
-    fn charger_offset(&self) -> Point {
-        match self {
-            ChargerAlignment::Left => Point::new(0, 0),
-            ChargerAlignment::Right => Point::new(15, 0),
-        }
-    }
+/// Method of visualizing the state of charge inside the battery
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BatteryFill {
+    /// Four separate bars, each representing a quarter of the charge
+    #[default]
+    Segments,
+    /// A single bar which grows with the charge
+    Continuous,
+}
+
+/// Position of the percentage label relative to the battery
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LabelPosition {
+    /// Inside the battery outline, instead of the fill
+    ///
+    /// The icon is scaled up until the label fits inside the battery, up to `MAX_SCALE`.
+    Inside,
+    /// Next to the battery, on the opposite side of the charger
+    Beside,
+    /// Centered below the battery
+    Below,
+}
+
+/// Placement of the parts of a battery icon, relative to its position
+struct IconLayout {
+    battery: Point,
+    charger: Point,
+    label: Option<Rectangle>,
+    size: Size,
 }
 
 #[derive(Copy, Clone)]
@@ -49,9 +154,244 @@ pub struct BatteryIcon<C> {
     state_of_charge: Option<StateOfCharge>,
     charger: Option<ChargerState>,
     charger_alignment: ChargerAlignment,
+    orientation: BatteryOrientation,
+    fill: BatteryFill,
+    scale: u32,
+    label: Option<LabelPosition>,
+    label_font: &'static MonoFont<'static>,
+    animation_time: Option<u32>,
     _phantom_data: PhantomData<C>,
 }
 
+impl<C> BatteryIcon<C> {
+    fn battery_size(&self) -> Size {
+        match self.orientation {
+            BatteryOrientation::Vertical => Size::new(BATTERY_WIDTH, BATTERY_HEIGHT) * self.scale,
+            BatteryOrientation::Horizontal => Size::new(BATTERY_HEIGHT, BATTERY_WIDTH) * self.scale,
+        }
+    }
+
+    fn charger_size(&self) -> Size {
+        Size::new(CHARGER_WIDTH, CHARGER_HEIGHT) * self.scale
+    }
+
+    fn label_size(&self) -> Size {
+        let font = self.label_font;
+        let characters = LABEL_MAX_CHARACTERS;
+        Size::new(
+            characters * font.character_size.width + (characters - 1) * font.character_spacing,
+            font.character_size.height,
+        )
+    }
+
+    fn layout(&self) -> IconLayout {
+        let battery_size = self.battery_size();
+        let charger_size = self.charger_size();
+        let label_size = self.label_size();
+        let spacing = LABEL_SPACING * self.scale;
+
+        let beside_size = match self.label {
+            Some(LabelPosition::Beside) => Size::new(label_size.width + spacing, label_size.height),
+            _ => Size::zero(),
+        };
+
+        let row_height = battery_size
+            .height
+            .max(charger_size.height)
+            .max(beside_size.height);
+        let centered = |height: u32| ((row_height - height) / 2) as i32;
+
+        let (battery_x, charger_x, beside_x) = match self.charger_alignment {
+            ChargerAlignment::Left => (
+                charger_size.width,
+                0,
+                charger_size.width + battery_size.width + spacing,
+            ),
+            ChargerAlignment::Right => {
+                (beside_size.width, beside_size.width + battery_size.width, 0)
+            }
+        };
+
+        let mut layout = IconLayout {
+            battery: Point::new(battery_x as i32, centered(battery_size.height)),
+            charger: Point::new(charger_x as i32, centered(charger_size.height)),
+            label: None,
+            size: Size::new(
+                beside_size.width + battery_size.width + charger_size.width,
+                row_height,
+            ),
+        };
+
+        match self.label {
+            None => {}
+            Some(LabelPosition::Inside) => {
+                let body = self.oriented(self.scaled(BATTERY_BODY));
+                layout.label = Some(body.translate(layout.battery));
+            }
+            Some(LabelPosition::Beside) => {
+                layout.label = Some(Rectangle::new(
+                    Point::new(beside_x as i32, centered(label_size.height)),
+                    label_size,
+                ));
+            }
+            Some(LabelPosition::Below) => {
+                let x =
+                    layout.battery.x + (battery_size.width as i32 - label_size.width as i32) / 2;
+                if x < 0 {
+                    layout.battery.x -= x;
+                    layout.charger.x -= x;
+                    layout.size.width += (-x) as u32;
+                }
+                let label = Rectangle::new(
+                    Point::new(x.max(0), (row_height + spacing) as i32),
+                    label_size,
+                );
+                layout.size = Size::new(
+                    layout
+                        .size
+                        .width
+                        .max(label.bottom_right().unwrap().x as u32 + 1),
+                    label.bottom_right().unwrap().y as u32 + 1,
+                );
+                layout.label = Some(label);
+            }
+        }
+
+        layout
+    }
+
+    /// Current frame of the charging animation, if it should be animated
+    fn animation_frame(&self) -> Option<u32> {
+        match self.charger {
+            Some(ChargerState::Charging) => {
+                self.animation_time.map(|time| time / ANIMATION_FRAME_MS)
+            }
+            _ => None,
+        }
+    }
+
+    /// Scale an area given in unscaled pixels of a vertical battery
+    fn scaled(&self, (x, y, width, height): (u32, u32, u32, u32)) -> Rectangle {
+        Rectangle::new(
+            Point::new((x * self.scale) as i32, (y * self.scale) as i32),
+            Size::new(width, height) * self.scale,
+        )
+    }
+
+    /// Rotate an area given in scaled pixels of a vertical battery to the configured orientation
+    fn oriented(&self, area: Rectangle) -> Rectangle {
+        match self.orientation {
+            BatteryOrientation::Vertical => area,
+            BatteryOrientation::Horizontal => {
+                let width = (BATTERY_HEIGHT * self.scale) as i32;
+                Rectangle::new(
+                    Point::new(
+                        width - area.top_left.y - area.size.height as i32,
+                        area.top_left.x,
+                    ),
+                    Size::new(area.size.height, area.size.width),
+                )
+            }
+        }
+    }
+}
+
+impl<C> BatteryIcon<C>
+where
+    C: RgbColor,
+{
+    fn draw_segments<D>(
+        &self,
+        display: &mut D,
+        state_of_charge: StateOfCharge,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let lit_segments = SEGMENTS
+            .iter()
+            .filter(|(threshold, _)| state_of_charge > *threshold)
+            .count();
+        let lit_segments = match self.animation_frame() {
+            Some(frame) => lit_segments + frame as usize % (SEGMENTS.len() + 1 - lit_segments),
+            None => lit_segments,
+        };
+
+        let color = if state_of_charge > StateOfCharge::from_percentage(20) {
+            C::WHITE
+        } else {
+            C::RED
+        };
+
+        let fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
+
+        for (_, area) in SEGMENTS.iter().take(lit_segments) {
+            self.oriented(self.scaled(*area))
+                .into_styled(fill_style)
+                .draw(display)?;
+        }
+
+        Ok(())
+    }
+
+    fn draw_symbol<D>(&self, display: &mut D, symbol: &Symbol, color: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let body = self.oriented(self.scaled(BATTERY_BODY));
+        let symbol_size = Size::new(SYMBOL_WIDTH, symbol.len() as u32) * self.scale;
+        let top_left = body.top_left + (body.size - symbol_size) / 2;
+
+        let fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
+
+        for (y, row) in symbol.iter().enumerate() {
+            for x in 0..SYMBOL_WIDTH {
+                if row & (1 << (SYMBOL_WIDTH - 1 - x)) != 0 {
+                    let position = Point::new(x as i32, y as i32) * self.scale as i32;
+                    Rectangle::new(top_left + position, Size::new(1, 1) * self.scale)
+                        .into_styled(fill_style)
+                        .draw(display)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_continuous<D>(
+        &self,
+        display: &mut D,
+        state_of_charge: StateOfCharge,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let body = self.scaled(BATTERY_BODY);
+        let mut height = state_of_charge.level() as u32 * body.size.height / 255;
+        if let Some(frame) = self.animation_frame() {
+            let frames = SEGMENTS.len() as u32;
+            height += (body.size.height - height) * (frame % (frames + 1)) / frames;
+        }
+        if height == 0 {
+            return Ok(());
+        }
+
+        let color = if state_of_charge > StateOfCharge::from_percentage(20) {
+            C::WHITE
+        } else {
+            C::RED
+        };
+
+        let fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
+
+        let area = Rectangle::new(
+            body.top_left + Point::new(0, (body.size.height - height) as i32),
+            Size::new(body.size.width, height),
+        );
+        self.oriented(area).into_styled(fill_style).draw(display)
+    }
+}
+
 impl<C> Drawable for BatteryIcon<C>
 where
     C: RgbColor,
@@ -64,79 +404,77 @@ where
     where
         D: DrawTarget<Color = C>,
     {
-        if let Some(state_of_charge) = self.state_of_charge {
-            let mut display =
-                display.translated(self.position + self.charger_alignment.battery_offset());
-
-            let border_color = if state_of_charge > StateOfCharge::from_percentage(10) {
-                C::WHITE
+        let layout = self.layout();
+        let symbol = self.charger.and_then(status_symbol).or_else(|| {
+            // Without a state of charge, mark the level as unknown instead of an empty battery
+            if self.state_of_charge.is_none() {
+                Some(&QUESTION_MARK)
             } else {
-                C::RED
-            };
-
-            let border_style = PrimitiveStyleBuilder::new()
-                .stroke_width(2)
-                .stroke_color(border_color)
-                .build();
-
-            Polyline::new(&[
-                Point::new(1, 24),
-                Point::new(1, 6),
-                Point::new(4, 6),
-                Point::new(4, 1),
-                Point::new(10, 1),
-                Point::new(10, 6),
-                Point::new(13, 6),
-                Point::new(13, 23),
-                Point::new(2, 23),
-            ])
-            .into_styled(border_style)
-            .draw(&mut display)?;
-
-            if state_of_charge > StateOfCharge::from_percentage(10) {
-                let color = if state_of_charge > StateOfCharge::from_percentage(20) {
-                    C::WHITE
-                } else {
-                    C::RED
-                };
-
-                let fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
-
-                Rectangle::new(Point::new(3, 18), Size::new(9, 4))
-                    .into_styled(fill_style)
-                    .draw(&mut display)?;
+                None
             }
+        });
 
-            if state_of_charge > StateOfCharge::from_percentage(35) {
-                let white_fill_style = PrimitiveStyleBuilder::new().fill_color(C::WHITE).build();
+        let mut battery_display = display.translated(self.position + layout.battery);
 
-                Rectangle::new(Point::new(3, 13), Size::new(9, 4))
-                    .into_styled(white_fill_style)
-                    .draw(&mut display)?;
+        let border_color = match (self.charger, self.state_of_charge) {
+            (Some(ChargerState::Fault), _) | (Some(ChargerState::OverTemperature), _) => C::RED,
+            (Some(ChargerState::Unknown), _) => C::WHITE,
+            (_, Some(state_of_charge)) if state_of_charge <= StateOfCharge::from_percentage(10) => {
+                C::RED
             }
+            _ => C::WHITE,
+        };
 
-            if state_of_charge > StateOfCharge::from_percentage(65) {
-                let white_fill_style = PrimitiveStyleBuilder::new().fill_color(C::WHITE).build();
+        let border_style = PrimitiveStyleBuilder::new()
+            .fill_color(border_color)
+            .build();
 
-                Rectangle::new(Point::new(3, 8), Size::new(9, 4))
-                    .into_styled(white_fill_style)
-                    .draw(&mut display)?;
+        for area in BATTERY_OUTLINE.iter() {
+            self.oriented(self.scaled(*area))
+                .into_styled(border_style)
+                .draw(&mut battery_display)?;
+        }
+
+        match (symbol, self.state_of_charge, self.label, self.fill) {
+            (Some(symbol), _, _, _) => {
+                self.draw_symbol(&mut battery_display, symbol, border_color)?
+            }
+            (None, _, Some(LabelPosition::Inside), _) | (None, None, _, _) => {}
+            (None, Some(state_of_charge), _, BatteryFill::Segments) => {
+                self.draw_segments(&mut battery_display, state_of_charge)?
+            }
+            (None, Some(state_of_charge), _, BatteryFill::Continuous) => {
+                self.draw_continuous(&mut battery_display, state_of_charge)?
             }
+        }
+
+        if let (Some(state_of_charge), Some(label_area)) = (self.state_of_charge, layout.label) {
+            let hidden_by_symbol =
+                symbol.is_some() && matches!(self.label, Some(LabelPosition::Inside));
+            if !hidden_by_symbol {
+                let mut text = String::<U4>::new();
+                write!(&mut text, "{}%", state_of_charge.percentage()).unwrap();
 
-            if state_of_charge > StateOfCharge::from_percentage(90) {
-                let white_fill_style = PrimitiveStyleBuilder::new().fill_color(C::WHITE).build();
+                let text_style = TextStyleBuilder::new()
+                    .alignment(Alignment::Center)
+                    .baseline(Baseline::Middle)
+                    .build();
 
-                Rectangle::new(Point::new(6, 3), Size::new(3, 4))
-                    .into_styled(white_fill_style)
-                    .draw(&mut display)?;
+                Text::with_text_style(
+                    &text,
+                    self.position + label_area.center(),
+                    MonoTextStyle::new(self.label_font, border_color),
+                    text_style,
+                )
+                .draw(display)?;
             }
         }
 
-        if let Some(charger) = self.charger {
-            if charger == ChargerState::Charging || charger == ChargerState::Full {
-                let offset = self.position + self.charger_alignment.charger_offset();
-
-                let color = if charger == ChargerState::Charging {
+        let offset = self.position + layout.charger;
+        let scale = self.scale as i32;
+        match self.charger {
+            Some(ChargerState::Charging) | Some(ChargerState::Full) => {
+                let color = if self.charger == Some(ChargerState::Charging) {
                     C::RED
                 } else {
                     C::GREEN
@@ -145,9 +483,9 @@ where
                 let yellow_fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
 
                 Triangle::new(
-                    Point::new(8, 1) + offset,
-                    Point::new(8, 14) + offset,
-                    Point::new(2, 14) + offset,
+                    Point::new(8, 1) * scale + offset,
+                    Point::new(8, 14) * scale + offset,
+                    Point::new(2, 14) * scale + offset,
                 )
                 .into_styled(yellow_fill_style)
                 .draw(display)?;
@@ -155,13 +493,26 @@ where
                 let yellow_fill_style = PrimitiveStyleBuilder::new().fill_color(color).build();
 
                 Triangle::new(
-                    Point::new(7, 10) + offset,
-                    Point::new(13, 10) + offset,
-                    Point::new(7, 23) + offset,
+                    Point::new(7, 10) * scale + offset,
+                    Point::new(13, 10) * scale + offset,
+                    Point::new(7, 23) * scale + offset,
                 )
                 .into_styled(yellow_fill_style)
                 .draw(display)?;
             }
+            Some(ChargerState::Paused) => {
+                let pause_style = PrimitiveStyleBuilder::new().fill_color(C::YELLOW).build();
+
+                for x in [3, 9].iter() {
+                    Rectangle::new(
+                        Point::new(*x, 6) * scale + offset,
+                        Size::new(3, 13) * self.scale,
+                    )
+                    .into_styled(pause_style)
+                    .draw(display)?;
+                }
+            }
+            _ => {}
         }
 
         Ok(())
@@ -186,7 +537,7 @@ impl<C> Dimensions for BatteryIcon<C> {
     fn bounding_box(&self) -> Rectangle {
         Rectangle {
             top_left: self.position,
-            size: Size::new(30, 25),
+            size: self.layout().size,
         }
     }
 }
@@ -203,11 +554,18 @@ impl<C> BatteryIconBuilder<C> {
                 state_of_charge: None,
                 charger: None,
                 charger_alignment: ChargerAlignment::Right,
+                orientation: BatteryOrientation::Vertical,
+                fill: BatteryFill::Segments,
+                scale: 1,
+                label: None,
+                label_font: &FONT_6X10,
+                animation_time: None,
                 _phantom_data: PhantomData,
             },
         }
     }
 
+    /// Level shown inside the battery, a question mark is drawn when it is not set
     pub fn with_state_of_charge(mut self, state_of_charge: StateOfCharge) -> Self {
         self.battery_icon.state_of_charge = Some(state_of_charge);
 
@@ -226,7 +584,75 @@ impl<C> BatteryIconBuilder<C> {
         self
     }
 
-    pub fn build(self) -> BatteryIcon<C> {
+    /// Draw the battery vertical (default) or horizontal
+    pub fn with_orientation(mut self, orientation: BatteryOrientation) -> Self {
+        self.battery_icon.orientation = orientation;
+
+        self
+    }
+
+    /// Draw the battery as separate segments (default) or as a continuous bar
+    pub fn with_fill(mut self, fill: BatteryFill) -> Self {
+        self.battery_icon.fill = fill;
+
+        self
+    }
+
+    /// Multiply the size of the icon by an integer factor (default 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics when the scale is 0 or larger than `MAX_SCALE`.
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        assert!(
+            scale > 0 && scale <= MAX_SCALE,
+            "invalid battery icon scale"
+        );
+        self.battery_icon.scale = scale;
+
+        self
+    }
+
+    /// Show the percentage of charge as text at the given position
+    pub fn with_percentage_label(mut self, position: LabelPosition) -> Self {
+        self.battery_icon.label = Some(position);
+
+        self
+    }
+
+    /// Font used for the percentage label (default `FONT_6X10`)
+    pub fn with_label_font(mut self, font: &'static MonoFont<'static>) -> Self {
+        self.battery_icon.label_font = font;
+
+        self
+    }
+
+    /// Animate the fill while charging, using the elapsed time in milliseconds
+    ///
+    /// The segments fill up one by one from the current state of charge. When the charger is
+    /// not charging, the static icon is drawn.
+    pub fn with_animation_time(mut self, elapsed_ms: u32) -> Self {
+        self.battery_icon.animation_time = Some(elapsed_ms);
+
+        self
+    }
+
+    pub fn build(mut self) -> BatteryIcon<C> {
+        let icon = &mut self.battery_icon;
+        if icon.label == Some(LabelPosition::Inside) {
+            let (_, _, width, height) = BATTERY_BODY;
+            let (body_width, body_height) = match icon.orientation {
+                BatteryOrientation::Vertical => (width, height),
+                BatteryOrientation::Horizontal => (height, width),
+            };
+            let label_size = icon.label_size();
+            icon.scale = icon
+                .scale
+                .max(label_size.width.div_ceil(body_width))
+                .max(label_size.height.div_ceil(body_height))
+                .min(MAX_SCALE);
+        }
+
         self.battery_icon
     }
 }
@@ -329,31 +755,425 @@ mod tests {
             .unwrap();
 
         display.assert_pattern(&[
-            "                ",
-            "        G       ",
-            "        G       ",
-            "       GG       ",
-            "       GG       ",
-            "      GGG       ",
-            "      GGG       ",
-            "     GGGG       ",
-            "     GGGG       ",
-            "    GGGGG       ",
-            "    GGGGGGGGGG  ",
-            "   GGGGGGGGGGG  ",
-            "   GGGGGGGGGG   ",
-            "  GGGGGGGGGGG   ",
-            "  GGGGGGGGGG    ",
-            "       GGGGG    ",
-            "       GGGG     ",
-            "       GGGG     ",
-            "       GGG      ",
-            "       GGG      ",
-            "       GG       ",
-            "       GG       ",
-            "       G        ",
-            "       G        ",
-            "                ",
+            "                  WWWWWWWWW    ",
+            "        G         WWWWWWWWW    ",
+            "        G         WW     WW    ",
+            "       GG         WW     WW    ",
+            "       GG         WW     WW    ",
+            "      GGG      WWWWW     WWWWW ",
+            "      GGG      WWWWW     WWWWW ",
+            "     GGGG      WW           WW ",
+            "     GGGG      WW           WW ",
+            "    GGGGG      WW           WW ",
+            "    GGGGGGGGGG WW           WW ",
+            "   GGGGGGGGGGG WW    WWW    WW ",
+            "   GGGGGGGGGG  WW   W   W   WW ",
+            "  GGGGGGGGGGG  WW       W   WW ",
+            "  GGGGGGGGGG   WW     WW    WW ",
+            "       GGGGG   WW     W     WW ",
+            "       GGGG    WW           WW ",
+            "       GGGG    WW     W     WW ",
+            "       GGG     WW           WW ",
+            "       GGG     WW           WW ",
+            "       GG      WW           WW ",
+            "       GG      WW           WW ",
+            "       G       WW           WW ",
+            "       G       WWWWWWWWWWWWWWW ",
+            "               WWWWWWWWWWWWWWW ",
+        ]);
+    }
+
+    #[test]
+    fn battery_horizontal() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        BatteryIconBuilder::new(Point::new(0, 0))
+            .with_state_of_charge(StateOfCharge::from_percentage(100))
+            .with_orientation(BatteryOrientation::Horizontal)
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "                         ",
+            "                         ",
+            "                         ",
+            "                         ",
+            "                         ",
+            "WWWWWWWWWWWWWWWWWWWW     ",
+            "WWWWWWWWWWWWWWWWWWWW     ",
+            "WW                WW     ",
+            "WW WWWW WWWW WWWW WWWWWWW",
+            "WW WWWW WWWW WWWW WWWWWWW",
+            "WW WWWW WWWW WWWW      WW",
+            "WW WWWW WWWW WWWW WWWW WW",
+            "WW WWWW WWWW WWWW WWWW WW",
+            "WW WWWW WWWW WWWW WWWW WW",
+            "WW WWWW WWWW WWWW      WW",
+            "WW WWWW WWWW WWWW WWWWWWW",
+            "WW WWWW WWWW WWWW WWWWWWW",
+            "WW                WW     ",
+            "WWWWWWWWWWWWWWWWWWWW     ",
+            "WWWWWWWWWWWWWWWWWWWW     ",
+        ]);
+    }
+
+    #[test]
+    fn battery_continuous_fill() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        BatteryIconBuilder::new(Point::new(0, 0))
+            .with_state_of_charge(StateOfCharge::from_percentage(50))
+            .with_fill(BatteryFill::Continuous)
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "   WWWWWWWWW   ",
+            "   WWWWWWWWW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "WWWWW     WWWWW",
+            "WWWWW     WWWWW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW WWWWWWWWW WW",
+            "WW           WW",
+            "WWWWWWWWWWWWWWW",
+            "WWWWWWWWWWWWWWW",
         ]);
     }
+
+    #[test]
+    #[should_panic]
+    fn scale_zero() {
+        BatteryIconBuilder::<Rgb888>::new(Point::zero()).with_scale(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_too_large() {
+        BatteryIconBuilder::<Rgb888>::new(Point::zero()).with_scale(MAX_SCALE + 1);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let icon: BatteryIcon<Rgb888> = BatteryIconBuilder::new(Point::new(1, 2)).build();
+        assert_eq!(
+            icon.bounding_box(),
+            Rectangle::new(Point::new(1, 2), Size::new(30, 25))
+        );
+
+        let icon: BatteryIcon<Rgb888> = BatteryIconBuilder::new(Point::new(1, 2))
+            .with_orientation(BatteryOrientation::Horizontal)
+            .with_scale(2)
+            .build();
+        assert_eq!(
+            icon.bounding_box(),
+            Rectangle::new(Point::new(1, 2), Size::new(80, 50))
+        );
+    }
+
+    #[test]
+    fn percentage_label_inside_max_scale() {
+        let icon = BatteryIconBuilder::<Rgb888>::new(Point::new(0, 0))
+            .with_state_of_charge(StateOfCharge::from_percentage(100))
+            .with_percentage_label(LabelPosition::Inside)
+            .with_label_font(&crate::font::OVERPASS_DIGITS_72)
+            .build();
+
+        assert_eq!(icon.scale, MAX_SCALE);
+    }
+
+    #[test]
+    fn percentage_label_inside() {
+        let builder = || {
+            BatteryIconBuilder::new(Point::new(0, 0))
+                .with_state_of_charge(StateOfCharge::from_percentage(100))
+                .with_orientation(BatteryOrientation::Horizontal)
+                .with_fill(BatteryFill::Continuous)
+        };
+
+        let mut without_label: MockDisplay<Rgb888> = MockDisplay::new();
+        builder()
+            .with_scale(2)
+            .build()
+            .draw(&mut without_label)
+            .unwrap();
+
+        let icon = builder()
+            .with_percentage_label(LabelPosition::Inside)
+            .build();
+        assert_eq!(icon.scale, 2);
+        let mut with_label: MockDisplay<Rgb888> = MockDisplay::new();
+        icon.draw(&mut with_label).unwrap();
+
+        // The label replaces the fill, the outline is unchanged
+        let body = icon.layout().label.unwrap();
+        let mut label_pixels = 0;
+        for point in Rectangle::new(Point::zero(), Size::new(64, 64)).points() {
+            if body.contains(point) {
+                label_pixels += with_label.get_pixel(point).is_some() as u32;
+            } else {
+                assert_eq!(with_label.get_pixel(point), without_label.get_pixel(point));
+            }
+        }
+        assert!(label_pixels > 0);
+    }
+
+    #[test]
+    fn percentage_label_below() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        let icon = BatteryIconBuilder::new(Point::new(0, 0))
+            .with_state_of_charge(StateOfCharge::from_percentage(5))
+            .with_percentage_label(LabelPosition::Below)
+            .build();
+        icon.draw(&mut display).unwrap();
+
+        assert_eq!(
+            icon.bounding_box(),
+            Rectangle::new(Point::new(0, 0), Size::new(34, 37))
+        );
+        display.assert_pattern(&[
+            "       RRRRRRRRR   ",
+            "       RRRRRRRRR   ",
+            "       RR     RR   ",
+            "       RR     RR   ",
+            "       RR     RR   ",
+            "    RRRRR     RRRRR",
+            "    RRRRR     RRRRR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RR           RR",
+            "    RRRRRRRRRRRRRRR",
+            "    RRRRRRRRRRRRRRR",
+            "                   ",
+            "                   ",
+            "                   ",
+            "      RRRRR  R  R  ",
+            "      R     R R R  ",
+            "      R RR   R R   ",
+            "      RR  R   R    ",
+            "          R  R R   ",
+            "      R   R R R R  ",
+            "       RRR  R  R   ",
+        ]);
+    }
+
+    #[test]
+    fn percentage_label_beside() {
+        let icon: BatteryIcon<Rgb888> = BatteryIconBuilder::new(Point::new(0, 0))
+            .with_percentage_label(LabelPosition::Beside)
+            .build();
+        assert_eq!(
+            icon.bounding_box(),
+            Rectangle::new(Point::new(0, 0), Size::new(56, 25))
+        );
+    }
+
+    #[test]
+    fn unknown_without_state_of_charge() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        BatteryIconBuilder::new(Point::new(0, 0))
+            .with_charger(ChargerState::Unknown)
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "   WWWWWWWWW   ",
+            "   WWWWWWWWW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "WWWWW     WWWWW",
+            "WWWWW     WWWWW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW    WWW    WW",
+            "WW   W   W   WW",
+            "WW       W   WW",
+            "WW     WW    WW",
+            "WW     W     WW",
+            "WW           WW",
+            "WW     W     WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WWWWWWWWWWWWWWW",
+            "WWWWWWWWWWWWWWW",
+        ]);
+    }
+
+    #[test]
+    fn missing_state_of_charge() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        BatteryIconBuilder::new(Point::new(0, 0))
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "   WWWWWWWWW   ",
+            "   WWWWWWWWW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "   WW     WW   ",
+            "WWWWW     WWWWW",
+            "WWWWW     WWWWW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW    WWW    WW",
+            "WW   W   W   WW",
+            "WW       W   WW",
+            "WW     WW    WW",
+            "WW     W     WW",
+            "WW           WW",
+            "WW     W     WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WW           WW",
+            "WWWWWWWWWWWWWWW",
+            "WWWWWWWWWWWWWWW",
+        ]);
+    }
+
+    #[test]
+    fn missing_state_of_charge_with_charger() {
+        let chargers = [
+            (ChargerState::Discharging, true),
+            (ChargerState::Charging, true),
+            (ChargerState::Full, true),
+            (ChargerState::Paused, true),
+            (ChargerState::Fault, false),
+            (ChargerState::OverTemperature, false),
+            (ChargerState::Unknown, true),
+        ];
+
+        for (charger, question_mark) in chargers.iter() {
+            let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+            display.set_allow_overdraw(true);
+
+            BatteryIconBuilder::new(Point::new(0, 0))
+                .with_charger(*charger)
+                .build()
+                .draw(&mut display)
+                .unwrap();
+
+            let outline = Point::new(0, 7);
+            let symbol = Point::new(7, 11);
+            let question_mark_only = Point::new(5, 12);
+            assert!(display.get_pixel(outline).is_some(), "{:?}", charger);
+            assert!(display.get_pixel(symbol).is_some(), "{:?}", charger);
+            assert_eq!(
+                display.get_pixel(question_mark_only).is_some(),
+                *question_mark,
+                "{:?}",
+                charger
+            );
+        }
+    }
+
+    #[test]
+    fn charging_paused() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        BatteryIconBuilder::new(Point::new(0, 0))
+            .with_state_of_charge(StateOfCharge::from_percentage(50))
+            .with_charger(ChargerState::Paused)
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        display.assert_pattern(&[
+            "   WWWWWWWWW                  ",
+            "   WWWWWWWWW                  ",
+            "   WW     WW                  ",
+            "   WW     WW                  ",
+            "   WW     WW                  ",
+            "WWWWW     WWWWW               ",
+            "WWWWW     WWWWW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW   YYY   YYY   ",
+            "WW           WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW   YYY   YYY   ",
+            "WW WWWWWWWWW WW               ",
+            "WW WWWWWWWWW WW               ",
+            "WW WWWWWWWWW WW               ",
+            "WW           WW               ",
+            "WWWWWWWWWWWWWWW               ",
+            "WWWWWWWWWWWWWWW               ",
+        ]);
+    }
+
+    #[test]
+    fn charging_animation() {
+        let draw = |percentage, charger, animation_time| {
+            let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            let mut builder = BatteryIconBuilder::new(Point::new(0, 0))
+                .with_state_of_charge(StateOfCharge::from_percentage(percentage))
+                .with_charger(charger);
+            if let Some(animation_time) = animation_time {
+                builder = builder.with_animation_time(animation_time);
+            }
+            builder.build().draw(&mut display).unwrap();
+            display
+        };
+
+        let charging = ChargerState::Charging;
+        assert_eq!(draw(50, charging, Some(0)), draw(50, charging, None));
+        assert_eq!(draw(50, charging, Some(600)), draw(80, charging, None));
+        assert_eq!(draw(50, charging, Some(1000)), draw(100, charging, None));
+        assert_eq!(draw(50, charging, Some(1500)), draw(50, charging, None));
+
+        let full = ChargerState::Full;
+        assert_eq!(draw(100, full, Some(600)), draw(100, full, None));
+    }
 }
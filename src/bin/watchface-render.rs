@@ -0,0 +1,262 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Render a watchface style to a PNG file, without a display
+//!
+//! ```bash
+//! cargo run --features render --bin watchface-render -- --style simple --time 10:09 \
+//!     --battery 75 --charger charging --output preview.png
+//! ```
+//!
+//! A declarative layout is drawn with `--layout examples/layouts/digital.json`. A photo can be
+//! drawn behind any style with `--background photo.bmp`.
+
+use chrono::Local;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process::exit;
+use watchface::battery::{ChargerState, StateOfCharge};
+use watchface::date::Date;
+use watchface::declarative::{DeclarativeStyle, Layout};
+use watchface::decorated::Decorated;
+use watchface::framebuffer::Framebuffer;
+use watchface::image_asset::ImageAsset;
+use watchface::profiler::{DrawProfiler, DrawReport};
+use watchface::time::Time;
+use watchface::{NamedStyle, StyleName, Watchface};
+
+const USAGE: &str = "Usage: watchface-render [OPTIONS]
+
+Options:
+  --style <STYLE>      simple, simple-anti-aliased, textual, word-clock, word-clock-english,
+                       binary, bcd or declarative (default: simple)
+  --layout <FILE>      JSON layout for the declarative style, selects that style
+  --image <NAME=FILE>  Image for the image elements of the layout with the name, can be
+                       repeated
+  --background <FILE>  Image to draw behind the style, in BMP, TGA or QOI format
+  --size <WxH>         Size of the image in pixels (default: 240x240)
+  --time <HH:MM[:SS]>  Time to show (default: the current time)
+  --date <YYYY-MM-DD>  Date to show (default: the current date)
+  --battery <PERCENT>  State of charge of the battery (0..100)
+  --charger <STATE>    discharging, charging, full, paused, over_temperature, fault or unknown
+  --output <FILE>      PNG file to write (default: watchface.png)
+  --profile            Print the draw cost of the style";
+
+struct Options {
+    style: String,
+    layout: Option<Layout>,
+    images: Vec<(String, Vec<u8>)>,
+    background: Option<Vec<u8>>,
+    size: Size,
+    time: Time,
+    date: Date,
+    battery: Option<StateOfCharge>,
+    charger: Option<ChargerState>,
+    output: String,
+    profile: bool,
+}
+
+/// Largest width or height that fits in a PNG image
+const MAX_PNG_DIMENSION: u32 = i32::MAX as u32;
+
+fn valid_png_size(size: Size) -> bool {
+    (1..=MAX_PNG_DIMENSION).contains(&size.width) && (1..=MAX_PNG_DIMENSION).contains(&size.height)
+}
+
+fn parse_size(value: &str) -> Option<Size> {
+    let (width, height) = value.split_once('x')?;
+    Some(Size::new(width.parse().ok()?, height.parse().ok()?)).filter(|&size| valid_png_size(size))
+}
+
+fn parse_battery(value: &str) -> Option<StateOfCharge> {
+    match value.parse() {
+        Ok(percentage) if percentage <= 100 => Some(StateOfCharge::from_percentage(percentage)),
+        _ => None,
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn read_layout(path: &str) -> Result<Layout, String> {
+    let json = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+    Layout::from_json(&json).map_err(|error| format!("{}: {}", path, error))
+}
+
+/// Parse the command line options, returns `None` when the usage is requested with `--help`
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let now = Local::now();
+    let mut options = Options {
+        style: String::from("simple"),
+        layout: None,
+        images: Vec::new(),
+        background: None,
+        size: Size::new(240, 240),
+        time: now.into(),
+        date: now.into(),
+        battery: None,
+        charger: None,
+        output: String::from("watchface.png"),
+        profile: false,
+    };
+
+    while let Some(option) = args.next() {
+        if option == "--help" {
+            return Ok(None);
+        }
+        if option == "--profile" {
+            options.profile = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", option))?;
+        let invalid = || format!("Invalid value for {}: {}", option, value);
+        match option.as_str() {
+            "--style" => options.style = value,
+            "--layout" => {
+                options.style = String::from("declarative");
+                options.layout = Some(read_layout(&value)?);
+            }
+            "--image" => {
+                let (name, path) = value.split_once('=').ok_or_else(invalid)?;
+                options.images.push((String::from(name), read_file(path)?));
+            }
+            "--background" => options.background = Some(read_file(&value)?),
+            "--size" => options.size = parse_size(&value).ok_or_else(invalid)?,
+            "--time" => options.time = value.parse().map_err(|_| invalid())?,
+            "--date" => options.date = value.parse().map_err(|_| invalid())?,
+            "--battery" => options.battery = Some(parse_battery(&value).ok_or_else(invalid)?),
+            "--charger" => options.charger = Some(value.parse().map_err(|_| invalid())?),
+            "--output" => options.output = value,
+            _ => return Err(format!("Unknown option: {}", option)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+/// Draw the background image behind a styled watchface, if there is one
+fn decorate<'a, T>(styled: T, background: Option<&'a ImageAsset<'a>>) -> Decorated<'a, T> {
+    match background {
+        Some(background) => Decorated::new(styled).with_background(background),
+        None => Decorated::new(styled),
+    }
+}
+
+fn render(options: &Options) -> Result<(Framebuffer<Rgb888>, DrawReport), String> {
+    let mut builder = Watchface::build()
+        .with_time(options.time)
+        .with_date(options.date);
+    if let Some(battery) = options.battery {
+        builder = builder.with_battery(battery);
+    }
+    if let Some(charger) = options.charger {
+        builder = builder.with_charger(charger);
+    }
+    let watchface = builder.finish();
+
+    let too_large = |error| format!("Size {}", error);
+    let framebuffer = Framebuffer::try_new(options.size, Rgb888::BLACK).map_err(too_large)?;
+    let mut profiler = DrawProfiler::try_new(framebuffer).map_err(too_large)?;
+    let display = &mut profiler;
+    let background = options
+        .background
+        .as_deref()
+        .map(ImageAsset::from_bytes)
+        .transpose()
+        .map_err(|error| format!("Background: {}", error))?;
+    let background = background.as_ref();
+    let images = options
+        .images
+        .iter()
+        .map(|(name, data)| {
+            ImageAsset::from_bytes(data)
+                .map(|image| (name.as_str(), image))
+                .map_err(|error| format!("Image {}: {}", name, error))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let result = if options.style == "declarative" {
+        let layout = options
+            .layout
+            .clone()
+            .ok_or("The declarative style needs a --layout")?;
+        let mut style = DeclarativeStyle::new(layout).map_err(|error| error.to_string())?;
+        for (name, image) in &images {
+            style = style.with_image(name, image);
+        }
+        decorate(watchface.into_styled(style), background).draw(display)
+    } else {
+        let name: StyleName = options
+            .style
+            .parse()
+            .map_err(|_| format!("Unknown style: {}", options.style))?;
+        decorate(watchface.into_styled(NamedStyle::new(name)), background).draw(display)
+    };
+    result.map_err(|error| format!("Drawing failed: {:?}", error))?;
+
+    let report = profiler.take_report();
+    Ok((profiler.into_inner(), report))
+}
+
+fn write_png(framebuffer: &Framebuffer<Rgb888>, path: &str) -> Result<(), String> {
+    let size = framebuffer.size();
+    if !valid_png_size(size) {
+        return Err(format!(
+            "{}: can't write a {}x{} image",
+            path, size.width, size.height
+        ));
+    }
+    let file = File::create(path).map_err(|error| format!("{}: {}", path, error))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size.width, size.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&framebuffer.to_rgb_bytes()))
+        .map_err(|error| format!("{}: {}", path, error))
+}
+
+fn main() {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            exit(1);
+        }
+    };
+
+    let result = render(&options).and_then(|(framebuffer, report)| {
+        if options.profile {
+            println!("{}", report);
+        }
+        write_png(&framebuffer, &options.output)
+    });
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        exit(1);
+    }
+}
@@ -0,0 +1,245 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::background::{Background, DrawOnBackground, PlainBackground};
+use crate::styled::Styled;
+use crate::theme::Theme;
+use crate::time::Time;
+use crate::watchface_data::Watchface;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Circle, PrimitiveStyle};
+use heapless::consts::*;
+use heapless::Vec;
+
+/// How the time is converted to bits
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BinaryMode {
+    /// One column for each of hours, minutes and seconds
+    #[default]
+    Binary,
+    /// Binary-coded decimal, one column for each decimal digit
+    Bcd,
+}
+
+/// A single column of the clock
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct BitColumn {
+    value: u8,
+    bits: u8,
+    seconds: bool,
+}
+
+impl BitColumn {
+    fn new(value: u8, bits: u8, seconds: bool) -> Self {
+        Self {
+            value,
+            bits,
+            seconds,
+        }
+    }
+}
+
+fn bit_columns(time: &Time, mode: BinaryMode) -> Vec<BitColumn, U6> {
+    let (hours, minutes, seconds) = (
+        time.hours_local(),
+        time.minutes_local(),
+        time.seconds_local(),
+    );
+    let columns: &[BitColumn] = match mode {
+        BinaryMode::Binary => &[
+            BitColumn::new(hours, 5, false),
+            BitColumn::new(minutes, 6, false),
+            BitColumn::new(seconds, 6, true),
+        ],
+        BinaryMode::Bcd => &[
+            BitColumn::new(hours / 10, 2, false),
+            BitColumn::new(hours % 10, 4, false),
+            BitColumn::new(minutes / 10, 3, false),
+            BitColumn::new(minutes % 10, 4, false),
+            BitColumn::new(seconds / 10, 3, true),
+            BitColumn::new(seconds % 10, 4, true),
+        ],
+    };
+
+    Vec::from_slice(columns).unwrap()
+}
+
+/// Binary clock watchface style
+///
+/// This draws the hours, minutes and seconds as columns of circles. A filled circle is a one and
+/// an empty circle is a zero, the least significant bit is at the bottom. The clock is scaled to
+/// fill the display.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::Drawable;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use watchface::time::Time;
+/// use watchface::{BinaryClockWatchfaceStyle, BinaryMode, Watchface};
+///
+/// let style = BinaryClockWatchfaceStyle::default().with_mode(BinaryMode::Bcd);
+///
+/// let styled_watchface = Watchface::build()
+///      .with_time(Time::from_values(12, 34, 56))
+///      .into_styled(style);
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_overdraw(true);
+/// styled_watchface.draw(&mut display);
+/// ```
+pub struct BinaryClockWatchfaceStyle<C> {
+    mode: BinaryMode,
+    theme: Theme<C>,
+}
+
+impl<C> Default for BinaryClockWatchfaceStyle<C>
+where
+    C: RgbColor,
+{
+    fn default() -> Self {
+        Self {
+            mode: BinaryMode::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl<C> BinaryClockWatchfaceStyle<C> {
+    /// Select true binary or binary-coded decimal (default binary)
+    pub fn with_mode(mut self, mode: BinaryMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Colors of the clock, the seconds are drawn in the accent color
+    pub fn with_theme(mut self, theme: Theme<C>) -> Self {
+        self.theme = theme;
+
+        self
+    }
+}
+
+impl<C> Drawable for Styled<Watchface, BinaryClockWatchfaceStyle<C>>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+    ) -> Result<(), <D as DrawTarget>::Error> {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, BinaryClockWatchfaceStyle<C>>
+where
+    C: PixelColor,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
+        let display_area = display.bounding_box();
+        let theme = &self.style.theme;
+
+        background.draw_background(&display_area, theme.background, display)?;
+
+        let time = match &self.watchface.time {
+            Some(time) => time,
+            None => return Ok(()),
+        };
+
+        let columns = bit_columns(time, self.style.mode);
+        let rows = columns.iter().map(|column| column.bits).max().unwrap_or(1) as u32;
+
+        let cell =
+            (display_area.size.width / columns.len() as u32).min(display_area.size.height / rows);
+        let diameter = cell * 3 / 4;
+        let stroke_width = (diameter / 10).max(1);
+        let origin = display_area.center()
+            - Size::new(columns.len() as u32 * cell, rows * cell) / 2
+            + Size::new(cell - diameter, cell - diameter) / 2;
+
+        for (x, column) in columns.iter().enumerate() {
+            let color = if column.seconds {
+                theme.accent
+            } else {
+                theme.foreground
+            };
+
+            for bit in 0..column.bits {
+                let style = if column.value & (1 << bit) != 0 {
+                    PrimitiveStyle::with_fill(color)
+                } else {
+                    PrimitiveStyle::with_stroke(color, stroke_width)
+                };
+                let y = rows - 1 - bit as u32;
+
+                Circle::new(
+                    origin + Point::new((x as u32 * cell) as i32, (y * cell) as i32),
+                    diameter,
+                )
+                .into_styled(style)
+                .draw(display)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_columns() {
+        let columns = bit_columns(&Time::from_values(23, 59, 7), BinaryMode::Binary);
+        assert_eq!(
+            columns,
+            [
+                BitColumn::new(23, 5, false),
+                BitColumn::new(59, 6, false),
+                BitColumn::new(7, 6, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn bcd_columns() {
+        let columns = bit_columns(&Time::from_values(23, 59, 7), BinaryMode::Bcd);
+        assert_eq!(
+            columns,
+            [
+                BitColumn::new(2, 2, false),
+                BitColumn::new(3, 4, false),
+                BitColumn::new(5, 3, false),
+                BitColumn::new(9, 4, false),
+                BitColumn::new(0, 3, true),
+                BitColumn::new(7, 4, true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,307 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use core::fmt;
+use core::str::FromStr;
+
+/// Simple representation of a date
+///
+/// Like `Time`, this is a simplified representation, so that it can also be used in applications
+/// without a full operating system or without chrono dependency. It is a day in the Gregorian
+/// calendar, in the local time zone.
+///
+/// # Examples
+/// ```
+/// use watchface::date::Date;
+///
+/// let date = Date::from_values(2021, 3, 4);
+/// assert!(date < Date::from_values(2021, 12, 1));
+/// assert_eq!(date.to_string(), "2021-03-04");
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+const fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Returns a date from the individual values
+    ///
+    /// # Arguments
+    /// * `year` - Year (0..=9999)
+    /// * `month` - Month (1..=12)
+    /// * `day` - Day of the month (1..=31, depending on the month)
+    ///
+    /// # Panics
+    /// Panics if one of the values is out of range, see `try_from_values` for a checked version.
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::date::Date;
+    /// let date = Date::from_values(2020, 9, 3);
+    /// assert_eq!(date.year(), 2020);
+    /// assert_eq!(date.month(), 9);
+    /// assert_eq!(date.day(), 3);
+    /// ```
+    pub const fn from_values(year: u16, month: u8, day: u8) -> Self {
+        match Self::try_from_values(year, month, day) {
+            Some(date) => date,
+            None => panic!("date value out of range"),
+        }
+    }
+
+    /// Returns a date from the individual values, or `None` if a value is out of range
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::date::Date;
+    /// assert!(Date::try_from_values(2020, 2, 29).is_some());
+    /// assert_eq!(Date::try_from_values(2021, 2, 29), None);
+    /// assert_eq!(Date::try_from_values(2021, 13, 1), None);
+    /// assert_eq!(Date::try_from_values(10000, 1, 1), None);
+    /// ```
+    pub const fn try_from_values(year: u16, month: u8, day: u8) -> Option<Self> {
+        if year <= 9999
+            && month >= 1
+            && month <= 12
+            && day >= 1
+            && day <= days_in_month(year, month)
+        {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Get the year
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the month (1..=12)
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month (1..=31)
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+/// Format a date as `YYYY-MM-DD`
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Error returned when parsing a `Date` fails
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct ParseDateError;
+
+/// Parse a date in the format `YYYY-MM-DD`
+///
+/// # Examples
+/// ```
+/// use watchface::date::Date;
+/// assert_eq!("2021-03-04".parse(), Ok(Date::from_values(2021, 3, 4)));
+/// assert!("2021-02-30".parse::<Date>().is_err());
+/// assert!("2021-03".parse::<Date>().is_err());
+/// ```
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+        let mut next = || parts.next().ok_or(ParseDateError);
+        let year = next()?.parse().map_err(|_| ParseDateError)?;
+        let month = next()?.parse().map_err(|_| ParseDateError)?;
+        let day = next()?.parse().map_err(|_| ParseDateError)?;
+        if next().is_ok() {
+            return Err(ParseDateError);
+        }
+        Date::try_from_values(year, month, day).ok_or(ParseDateError)
+    }
+}
+
+#[cfg(feature = "chrono")]
+use chrono::prelude::*;
+
+/// Create a date from a chrono NaiveDate, years outside of 0..=9999 are clamped to that range
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use watchface::date::Date;
+///
+/// let date = NaiveDate::from_ymd_opt(2020, 9, 3).unwrap();
+/// assert_eq!(Date::from(date), Date::from_values(2020, 9, 3));
+/// ```
+#[cfg(feature = "chrono")]
+impl From<NaiveDate> for Date {
+    fn from(date: NaiveDate) -> Self {
+        match date.year() {
+            year if year < 0 => Date::from_values(0, 1, 1),
+            year if year > 9999 => Date::from_values(9999, 12, 31),
+            year => Date {
+                year: year as u16,
+                month: date.month() as u8,
+                day: date.day() as u8,
+            },
+        }
+    }
+}
+
+/// Create a date from a chrono NaiveDateTime
+#[cfg(feature = "chrono")]
+impl From<NaiveDateTime> for Date {
+    fn from(date_time: NaiveDateTime) -> Self {
+        date_time.date().into()
+    }
+}
+
+/// Create a date from the local date of a chrono DateTime in any time zone
+///
+/// # Examples
+/// ```
+/// use chrono::{FixedOffset, TimeZone, Utc};
+/// use watchface::date::Date;
+///
+/// let utc = Utc.with_ymd_and_hms(2020, 9, 3, 23, 23, 2).unwrap();
+/// assert_eq!(Date::from(utc), Date::from_values(2020, 9, 3));
+///
+/// let amsterdam = utc.with_timezone(&FixedOffset::east_opt(2 * 60 * 60).unwrap());
+/// assert_eq!(Date::from(amsterdam), Date::from_values(2020, 9, 4));
+/// ```
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for Date {
+    fn from(date_time: DateTime<Tz>) -> Self {
+        date_time.naive_local().into()
+    }
+}
+
+/// Create a date from a `time` crate Date, years outside of 0..=9999 are clamped to that range
+///
+/// # Examples
+/// ```
+/// use time::Month;
+/// use watchface::date::Date;
+///
+/// let date = time::Date::from_calendar_date(2020, Month::September, 3).unwrap();
+/// assert_eq!(Date::from(date), Date::from_values(2020, 9, 3));
+/// ```
+#[cfg(feature = "time")]
+impl From<::time::Date> for Date {
+    fn from(date: ::time::Date) -> Self {
+        match date.year() {
+            year if year < 0 => Date::from_values(0, 1, 1),
+            year if year > 9999 => Date::from_values(9999, 12, 31),
+            year => Date {
+                year: year as u16,
+                month: date.month() as u8,
+                day: date.day(),
+            },
+        }
+    }
+}
+
+/// Create a date from a `time` crate PrimitiveDateTime
+#[cfg(feature = "time")]
+impl From<::time::PrimitiveDateTime> for Date {
+    fn from(date_time: ::time::PrimitiveDateTime) -> Self {
+        date_time.date().into()
+    }
+}
+
+/// Create a date from the local date of a `time` crate OffsetDateTime
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for Date {
+    fn from(date_time: ::time::OffsetDateTime) -> Self {
+        date_time.date().into()
+    }
+}
+
+#[cfg(feature = "rtcc")]
+impl Date {
+    /// Read the current date from a real-time clock
+    ///
+    /// The clock is expected to run in local time.
+    pub fn from_rtc<R: rtcc::DateTimeAccess>(rtc: &mut R) -> Result<Date, R::Error> {
+        rtc.datetime().map(Date::from)
+    }
+}
+
+/// Serialize a date as a `YYYY-MM-DD` string in human-readable formats, otherwise as a tuple of
+/// year, month and day
+#[cfg(feature = "serde")]
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.year, self.month, self.day).serialize(serializer)
+        }
+    }
+}
+
+/// Deserialize a date from a `YYYY-MM-DD` string in human-readable formats, otherwise from a
+/// tuple of year, month and day
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected, Visitor};
+
+        struct DateVisitor;
+
+        impl<'de> Visitor<'de> for DateVisitor {
+            type Value = Date;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a date in the format YYYY-MM-DD")
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Date, E> {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DateVisitor)
+        } else {
+            let (year, month, day) = serde::Deserialize::deserialize(deserializer)?;
+            Date::try_from_values(year, month, day)
+                .ok_or_else(|| D::Error::custom("date value out of range"))
+        }
+    }
+}
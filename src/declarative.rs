@@ -0,0 +1,1039 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Watchface layouts described as data
+//!
+//! A `Layout` lists the elements of a watchface, so it can be designed without writing Rust. It
+//! is parsed with `Layout::from_json` without `std` and alloc, or from a compact binary format
+//! like postcard using serde. The `DeclarativeStyle` draws a `Watchface` according to it.
+//!
+//! Each element is placed by aligning it to the display, like the other styles do, and then
+//! moving it by an offset. Missing fields have a default value, values out of range are rejected
+//! by `Layout::validate`, which `DeclarativeStyle::new` calls as well. Image elements refer to an
+//! image by name, the images themselves are provided to the style with
+//! `DeclarativeStyle::with_image`.
+//!
+//! ```json
+//! {
+//!   "background": [0, 0, 48],
+//!   "elements": [
+//!     { "image": { "name": "logo", "placement": { "vertical": "bottom" } } },
+//!     { "time": { "font": "overpass_anti_aliased_64" } },
+//!     { "time": { "format": "seconds", "font": "overpass_24", "color": [0, 255, 255],
+//!                 "placement": { "vertical": "center", "offset": [0, 50] } } },
+//!     { "date": { "format": "day_month", "font": "overpass_24",
+//!                 "placement": { "vertical": "center", "offset": [0, -50] } } },
+//!     { "rectangle": { "size": [160, 3], "fill": [0, 255, 255],
+//!                      "placement": { "offset": [0, 35] } } },
+//!     { "battery": { "scale": 2, "charger": "left",
+//!                    "placement": { "horizontal": "right", "vertical": "top",
+//!                                   "offset": [-10, 10] } } }
+//!   ]
+//! }
+//! ```
+
+use crate::anti_aliased_font::AntiAliasedText;
+use crate::background::{Background, DrawOnBackground, PlainBackground};
+use crate::battery_icon::{
+    BatteryFill, BatteryIconBuilder, BatteryOrientation, ChargerAlignment, LabelPosition, MAX_SCALE,
+};
+use crate::date::Date;
+use crate::font::{
+    OVERPASS_DIGITS_24, OVERPASS_DIGITS_40, OVERPASS_DIGITS_72, OVERPASS_DIGITS_AA_64,
+    OVERPASS_DIGITS_PROPORTIONAL_64, OVERPASS_NUMBERS_FONT,
+};
+#[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+use crate::image_asset::ImageAsset;
+use crate::proportional_font::ProportionalText;
+use crate::styled::Styled;
+use crate::time::Time;
+use crate::watchface_data::Watchface;
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+#[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+use embedded_graphics::image::Image;
+use embedded_graphics::{
+    mono_font::MonoTextStyleBuilder,
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyleBuilder, Rectangle},
+    text::Text,
+};
+use embedded_layout::prelude::*;
+use heapless::consts::*;
+use heapless::{String, Vec};
+use serde::{Deserialize, Serialize};
+
+/// Description of a watchface with up to 16 elements, drawn in order
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    /// Color of the empty screen
+    pub background: Color,
+    pub elements: Vec<Element, U16>,
+}
+
+impl Layout {
+    /// Parse a layout from JSON
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use watchface::declarative::{Element, Layout};
+    ///
+    /// let layout = Layout::from_json(r#"{ "elements": [ { "time": {} } ] }"#).unwrap();
+    /// assert!(matches!(layout.elements[0], Element::Time(_)));
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, ParseLayoutError> {
+        let (layout, _): (Layout, _) = serde_json_core::from_str(json)
+            .map_err(|error| ParseLayoutError(LayoutErrorKind::Json(error)))?;
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    /// Check that the values of the elements are in range, so that the layout can be drawn
+    ///
+    /// Battery scales must be from 1 to `battery_icon::MAX_SCALE`. Rectangle sizes, stroke widths
+    /// and offsets are at most `MAX_DIMENSION` pixels. Image elements need one of the image format
+    /// features. `from_json` and `DeclarativeStyle::new` check this already.
+    pub fn validate(&self) -> Result<(), ParseLayoutError> {
+        for (index, element) in self.elements.iter().enumerate() {
+            let invalid = |message| ParseLayoutError(LayoutErrorKind::Invalid { index, message });
+
+            let placement = match element {
+                Element::Time(time) => &time.placement,
+                Element::Date(date) => &date.placement,
+                Element::Battery(battery) => {
+                    if battery.scale == 0 || battery.scale > MAX_SCALE {
+                        return Err(invalid("battery scale out of range"));
+                    }
+                    &battery.placement
+                }
+                Element::Rectangle(rectangle) => {
+                    if rectangle.size.iter().any(|size| *size > MAX_DIMENSION) {
+                        return Err(invalid("rectangle size too large"));
+                    }
+                    if rectangle.stroke_width > MAX_DIMENSION {
+                        return Err(invalid("rectangle stroke width too large"));
+                    }
+                    &rectangle.placement
+                }
+                #[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+                Element::Image(image) => &image.placement,
+                #[cfg(not(any(feature = "bmp", feature = "tga", feature = "qoi")))]
+                Element::Image(_) => return Err(invalid("images are not supported")),
+            };
+
+            let in_range = |offset: &i32| offset.unsigned_abs() <= MAX_DIMENSION;
+            if !placement.offset.iter().all(in_range) {
+                return Err(invalid("offset out of range"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Largest rectangle size, stroke width and offset in a layout, in pixels
+pub const MAX_DIMENSION: u32 = 4096;
+
+/// Error returned when a layout is not valid JSON, doesn't match the schema or has values out of
+/// range
+#[derive(Debug)]
+pub struct ParseLayoutError(LayoutErrorKind);
+
+#[derive(Debug)]
+enum LayoutErrorKind {
+    Json(serde_json_core::de::Error),
+    Invalid { index: usize, message: &'static str },
+}
+
+impl fmt::Display for ParseLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            LayoutErrorKind::Json(error) => write!(f, "invalid layout: {}", error),
+            LayoutErrorKind::Invalid { index, message } => {
+                write!(f, "invalid layout: element {}: {}", index, message)
+            }
+        }
+    }
+}
+
+/// An RGB color, written as `[red, green, blue]`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Color(pub [u8; 3]);
+
+impl Color {
+    pub const WHITE: Color = Color([255, 255, 255]);
+
+    fn into_color<C: From<Rgb888>>(self) -> C {
+        let [r, g, b] = self.0;
+        Rgb888::new(r, g, b).into()
+    }
+}
+
+/// A part of the watchface
+///
+/// The element is written as an object with the snake case name as the only key, like
+/// `{ "time": { ... } }`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Element {
+    Time(TimeElement),
+    Date(DateElement),
+    Battery(BatteryElement),
+    Rectangle(RectangleElement),
+    Image(ImageElement),
+}
+
+/// The time as text, not drawn when the watchface has no time
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeElement {
+    pub format: TimeFormat,
+    pub font: Font,
+    pub color: Color,
+    pub placement: Placement,
+}
+
+impl Default for TimeElement {
+    fn default() -> Self {
+        Self {
+            format: TimeFormat::default(),
+            font: Font::default(),
+            color: Color::WHITE,
+            placement: Placement::default(),
+        }
+    }
+}
+
+/// The date as text, not drawn when the watchface has no date
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DateElement {
+    pub format: DateFormat,
+    pub font: Font,
+    pub color: Color,
+    pub placement: Placement,
+}
+
+impl Default for DateElement {
+    fn default() -> Self {
+        Self {
+            format: DateFormat::default(),
+            font: Font::default(),
+            color: Color::WHITE,
+            placement: Placement::default(),
+        }
+    }
+}
+
+/// The battery icon, see `BatteryIconBuilder` for the options
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatteryElement {
+    pub scale: u32,
+    pub orientation: BatteryOrientation,
+    pub fill: BatteryFill,
+    /// Side of the charger symbol
+    pub charger: ChargerAlignment,
+    pub label: Option<LabelPosition>,
+    pub placement: Placement,
+}
+
+impl Default for BatteryElement {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            orientation: BatteryOrientation::default(),
+            fill: BatteryFill::default(),
+            charger: ChargerAlignment::default(),
+            label: None,
+            placement: Placement::default(),
+        }
+    }
+}
+
+/// A filled and/or outlined rectangle, for decoration
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RectangleElement {
+    /// Width and height in pixels
+    pub size: [u32; 2],
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub stroke_width: u32,
+    pub placement: Placement,
+}
+
+impl Default for RectangleElement {
+    fn default() -> Self {
+        Self {
+            size: [0, 0],
+            fill: None,
+            stroke: None,
+            stroke_width: 1,
+            placement: Placement::default(),
+        }
+    }
+}
+
+/// An image provided with `DeclarativeStyle::with_image`
+///
+/// The image is not drawn when no image with the name is provided. Layouts with images are
+/// rejected when none of the image format features are enabled.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageElement {
+    pub name: String<U16>,
+    pub placement: Placement,
+}
+
+/// Part of the time shown by a `TimeElement`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormat {
+    /// `HH:MM`
+    #[default]
+    HoursMinutes,
+    /// `HH:MM:SS`
+    HoursMinutesSeconds,
+    /// `HH`
+    Hours,
+    /// `MM`
+    Minutes,
+    /// `SS`
+    Seconds,
+}
+
+impl TimeFormat {
+    fn format(self, time: &Time) -> String<U8> {
+        let (hours, minutes, seconds) = (
+            time.hours_local(),
+            time.minutes_local(),
+            time.seconds_local(),
+        );
+        let mut text = String::new();
+        match self {
+            TimeFormat::HoursMinutes => write!(text, "{:02}:{:02}", hours, minutes),
+            TimeFormat::HoursMinutesSeconds => {
+                write!(text, "{:02}:{:02}:{:02}", hours, minutes, seconds)
+            }
+            TimeFormat::Hours => write!(text, "{:02}", hours),
+            TimeFormat::Minutes => write!(text, "{:02}", minutes),
+            TimeFormat::Seconds => write!(text, "{:02}", seconds),
+        }
+        .unwrap();
+        text
+    }
+}
+
+/// Parts of the date shown by a `DateElement`
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateFormat {
+    /// `YYYY-MM-DD`
+    #[default]
+    YearMonthDay,
+    /// `DD.MM`
+    DayMonth,
+    /// `DD-MM-YYYY`
+    DayMonthYear,
+    /// `DD`
+    Day,
+    /// `MM`
+    Month,
+    /// `YYYY`
+    Year,
+}
+
+impl DateFormat {
+    fn format(self, date: &Date) -> String<U16> {
+        let (year, month, day) = (date.year(), date.month(), date.day());
+        let mut text = String::new();
+        match self {
+            DateFormat::YearMonthDay => write!(text, "{:04}-{:02}-{:02}", year, month, day),
+            DateFormat::DayMonth => write!(text, "{:02}.{:02}", day, month),
+            DateFormat::DayMonthYear => write!(text, "{:02}-{:02}-{:04}", day, month, year),
+            DateFormat::Day => write!(text, "{:02}", day),
+            DateFormat::Month => write!(text, "{:02}", month),
+            DateFormat::Year => write!(text, "{:04}", year),
+        }
+        .unwrap();
+        text
+    }
+}
+
+/// One of the fonts from the `font` module
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Font {
+    /// `OVERPASS_DIGITS_24`
+    #[serde(rename = "overpass_24")]
+    Overpass24,
+    /// `OVERPASS_DIGITS_40`
+    #[serde(rename = "overpass_40")]
+    Overpass40,
+    /// `OVERPASS_NUMBERS_FONT`, as used by `SimpleWatchfaceStyle`
+    #[default]
+    #[serde(rename = "overpass_52")]
+    Overpass52,
+    /// `OVERPASS_DIGITS_72`
+    #[serde(rename = "overpass_72")]
+    Overpass72,
+    /// `OVERPASS_DIGITS_AA_64`, blended with the layout background
+    #[serde(rename = "overpass_anti_aliased_64")]
+    OverpassAntiAliased64,
+    /// `OVERPASS_DIGITS_PROPORTIONAL_64`
+    #[serde(rename = "overpass_proportional_64")]
+    OverpassProportional64,
+}
+
+/// Horizontal alignment to the display
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Horizontal {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+/// Vertical alignment to the display
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Vertical {
+    Top,
+    #[default]
+    Center,
+    Bottom,
+}
+
+/// Position of an element, centered on the display by default
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Placement {
+    pub horizontal: Horizontal,
+    pub vertical: Vertical,
+    /// Movement in pixels after aligning, `[x, y]`
+    pub offset: [i32; 2],
+}
+
+impl Placement {
+    fn place<V: View>(&self, view: V, display_area: &Rectangle) -> V {
+        let view = match self.horizontal {
+            Horizontal::Left => {
+                view.align_to(display_area, horizontal::Left, vertical::NoAlignment)
+            }
+            Horizontal::Center => {
+                view.align_to(display_area, horizontal::Center, vertical::NoAlignment)
+            }
+            Horizontal::Right => {
+                view.align_to(display_area, horizontal::Right, vertical::NoAlignment)
+            }
+        };
+        let mut view = match self.vertical {
+            Vertical::Top => view.align_to(display_area, horizontal::NoAlignment, vertical::Top),
+            Vertical::Center => {
+                view.align_to(display_area, horizontal::NoAlignment, vertical::Center)
+            }
+            Vertical::Bottom => {
+                view.align_to(display_area, horizontal::NoAlignment, vertical::Bottom)
+            }
+        };
+        let [x, y] = self.offset;
+        View::translate_impl(&mut view, Point::new(x, y));
+        view
+    }
+}
+
+/// Watchface style drawn according to a `Layout`
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::Drawable;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use watchface::declarative::{DeclarativeStyle, Layout};
+/// use watchface::time::Time;
+/// use watchface::Watchface;
+///
+/// let layout = Layout::from_json(
+///     r#"{
+///         "background": [0, 0, 48],
+///         "elements": [
+///             { "time": { "font": "overpass_24" } },
+///             { "battery": { "placement": { "horizontal": "right", "vertical": "top" } } }
+///         ]
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// let styled_watchface = Watchface::build()
+///     .with_time(Time::from_values(10, 9, 0))
+///     .into_styled(DeclarativeStyle::new(layout).unwrap());
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_out_of_bounds_drawing(true);
+/// display.set_allow_overdraw(true);
+/// styled_watchface.draw(&mut display).unwrap();
+/// ```
+pub struct DeclarativeStyle<'a, C> {
+    layout: Layout,
+    #[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+    images: Vec<(&'a str, &'a ImageAsset<'a>), U8>,
+    _phantom_data: PhantomData<(&'a (), C)>,
+}
+
+impl<'a, C> DeclarativeStyle<'a, C> {
+    /// Style for drawing a layout, fails when the layout does not pass `Layout::validate`
+    pub fn new(layout: Layout) -> Result<Self, ParseLayoutError> {
+        layout.validate()?;
+
+        Ok(Self {
+            layout,
+            #[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+            images: Vec::new(),
+            _phantom_data: PhantomData,
+        })
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// Provide the image drawn by the image elements with the given name, up to 8 images
+    ///
+    /// # Panics
+    ///
+    /// Panics when more than 8 images are added.
+    #[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+    pub fn with_image(mut self, name: &'a str, image: &'a ImageAsset<'a>) -> Self {
+        if self.images.push((name, image)).is_err() {
+            panic!("too many images");
+        }
+
+        self
+    }
+}
+
+/// Draw the text of a time or date element, anti-aliased text is blended with the background
+fn draw_text<C, D, B>(
+    text: &str,
+    font: Font,
+    color: Color,
+    placement: &Placement,
+    background_color: C,
+    background: &B,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    C: RgbColor + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+    B: Background<C>,
+{
+    let display_area = display.bounding_box();
+    let color = color.into_color();
+
+    let mono_font = match font {
+        Font::Overpass24 => &OVERPASS_DIGITS_24,
+        Font::Overpass40 => &OVERPASS_DIGITS_40,
+        Font::Overpass52 => &OVERPASS_NUMBERS_FONT,
+        Font::Overpass72 => &OVERPASS_DIGITS_72,
+        Font::OverpassAntiAliased64 => {
+            let text = AntiAliasedText::new(
+                text,
+                Point::zero(),
+                &OVERPASS_DIGITS_AA_64,
+                color,
+                background_color,
+            );
+            return placement
+                .place(text, &display_area)
+                .draw_on_background(display, background);
+        }
+        Font::OverpassProportional64 => {
+            let text =
+                ProportionalText::new(text, Point::zero(), &OVERPASS_DIGITS_PROPORTIONAL_64, color);
+            return placement.place(text, &display_area).draw(display);
+        }
+    };
+
+    let text_style = MonoTextStyleBuilder::new()
+        .font(mono_font)
+        .text_color(color)
+        .build();
+    placement
+        .place(Text::new(text, Point::zero(), text_style), &display_area)
+        .draw(display)?;
+
+    Ok(())
+}
+
+fn draw_battery<C, D>(
+    element: &BatteryElement,
+    watchface: &Watchface,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    C: RgbColor + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+{
+    let mut icon_builder = BatteryIconBuilder::new(Point::zero())
+        .with_scale(element.scale)
+        .with_orientation(element.orientation)
+        .with_fill(element.fill)
+        .with_charger_alignment(element.charger);
+    if let Some(label) = element.label {
+        icon_builder = icon_builder.with_percentage_label(label);
+    }
+    if let Some(battery) = &watchface.battery {
+        icon_builder = icon_builder.with_state_of_charge(*battery);
+    }
+    if let Some(charger) = &watchface.charger {
+        icon_builder = icon_builder.with_charger(*charger);
+    }
+    if let Some(animation_time) = watchface.animation_time {
+        icon_builder = icon_builder.with_animation_time(animation_time);
+    }
+
+    let display_area = display.bounding_box();
+    element
+        .placement
+        .place(icon_builder.build(), &display_area)
+        .draw(display)
+}
+
+fn draw_rectangle<C, D>(element: &RectangleElement, display: &mut D) -> Result<(), D::Error>
+where
+    C: RgbColor + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+{
+    let mut style = PrimitiveStyleBuilder::new();
+    if let Some(fill) = element.fill {
+        style = style.fill_color(fill.into_color());
+    }
+    if let Some(stroke) = element.stroke {
+        style = style
+            .stroke_color(stroke.into_color())
+            .stroke_width(element.stroke_width);
+    }
+
+    let [width, height] = element.size;
+    let display_area = display.bounding_box();
+    element
+        .placement
+        .place(
+            Rectangle::new(Point::zero(), Size::new(width, height)).into_styled(style.build()),
+            &display_area,
+        )
+        .draw(display)
+}
+
+#[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+fn draw_image<C, D>(
+    element: &ImageElement,
+    images: &[(&str, &ImageAsset<'_>)],
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    C: RgbColor + From<Rgb888>,
+    D: DrawTarget<Color = C>,
+{
+    let image = match images
+        .iter()
+        .find(|(name, _)| *name == element.name.as_str())
+    {
+        Some((_, image)) => *image,
+        None => return Ok(()),
+    };
+
+    let display_area = display.bounding_box();
+    element
+        .placement
+        .place(Image::new(image, Point::zero()), &display_area)
+        .draw(&mut display.color_converted())
+}
+
+impl<C> Drawable for Styled<Watchface, DeclarativeStyle<'_, C>>
+where
+    C: RgbColor + From<Rgb888>,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+    ) -> Result<(), <D as DrawTarget>::Error> {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, DeclarativeStyle<'_, C>>
+where
+    C: RgbColor + From<Rgb888>,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
+        let display_area = display.bounding_box();
+        let layout = &self.style.layout;
+        let background_color = layout.background.into_color();
+
+        background.draw_background(&display_area, background_color, display)?;
+
+        for element in &layout.elements {
+            match element {
+                Element::Time(element) => {
+                    if let Some(time) = &self.watchface.time {
+                        let text = element.format.format(time);
+                        draw_text(
+                            &text,
+                            element.font,
+                            element.color,
+                            &element.placement,
+                            background_color,
+                            background,
+                            display,
+                        )?;
+                    }
+                }
+                Element::Date(element) => {
+                    if let Some(date) = &self.watchface.date {
+                        let text = element.format.format(date);
+                        draw_text(
+                            &text,
+                            element.font,
+                            element.color,
+                            &element.placement,
+                            background_color,
+                            background,
+                            display,
+                        )?;
+                    }
+                }
+                Element::Battery(element) => draw_battery(element, &self.watchface, display)?,
+                Element::Rectangle(element) => draw_rectangle(element, display)?,
+                #[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+                Element::Image(element) => draw_image(element, &self.style.images, display)?,
+                #[cfg(not(any(feature = "bmp", feature = "tga", feature = "qoi")))]
+                Element::Image(_) => unreachable!("rejected by Layout::validate"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    #[test]
+    fn json_defaults() {
+        let layout = Layout::from_json(
+            r#"{
+                "elements": [
+                    { "time": { "format": "seconds" } },
+                    { "rectangle": { "size": [4, 2], "placement": { "vertical": "top" } } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(layout.background, Color([0, 0, 0]));
+        assert_eq!(
+            layout.elements[0],
+            Element::Time(TimeElement {
+                format: TimeFormat::Seconds,
+                ..TimeElement::default()
+            })
+        );
+        assert_eq!(
+            layout.elements[1],
+            Element::Rectangle(RectangleElement {
+                size: [4, 2],
+                placement: Placement {
+                    vertical: Vertical::Top,
+                    ..Placement::default()
+                },
+                ..RectangleElement::default()
+            })
+        );
+
+        let layout = Layout::from_json(r#"{ "elements": [ { "date": {} } ] }"#).unwrap();
+        assert_eq!(layout.elements[0], Element::Date(DateElement::default()));
+        assert!(Layout::from_json(r#"{ "elements": [ { "weather": {} } ] }"#).is_err());
+        assert!(Layout::from_json(r#"{ "background": [0, 0] }"#).is_err());
+    }
+
+    #[test]
+    fn example_layout() {
+        let layout = Layout::from_json(include_str!("../examples/layouts/digital.json")).unwrap();
+        assert_eq!(layout.elements.len(), 5);
+    }
+
+    #[test]
+    fn postcard_round_trip() {
+        let layout = Layout::from_json(
+            r#"{ "elements": [ { "battery": { "scale": 2, "label": "below" } } ] }"#,
+        )
+        .unwrap();
+
+        let mut buffer = [0; 32];
+        let encoded = postcard::to_slice(&layout, &mut buffer).unwrap();
+        assert_eq!(postcard::from_bytes::<Layout>(encoded).unwrap(), layout);
+    }
+
+    #[cfg(feature = "bmp")]
+    #[test]
+    fn named_images() {
+        let layout = Layout::from_json(
+            r#"{
+                "elements": [
+                    { "image": { "name": "pattern",
+                                 "placement": { "horizontal": "left", "vertical": "top" } } },
+                    { "image": { "name": "missing" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let image = ImageAsset::from_bytes(include_bytes!("../tests/images/pattern.bmp")).unwrap();
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        Watchface::default()
+            .into_styled(
+                DeclarativeStyle::new(layout)
+                    .unwrap()
+                    .with_image("pattern", &image),
+            )
+            .draw(&mut display.clipped(&Rectangle::new(Point::zero(), Size::new(5, 4))))
+            .unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "RGBWK",
+            "KYCMK",
+            "WKWKK",
+            "KKKKK",
+        ]);
+    }
+
+    #[test]
+    fn placed_rectangles() {
+        let layout = Layout::from_json(
+            r#"{
+                "background": [0, 0, 255],
+                "elements": [
+                    { "rectangle": { "size": [2, 2], "fill": [255, 0, 0],
+                                     "placement": { "horizontal": "left", "vertical": "top" } } },
+                    { "rectangle": { "size": [3, 2], "stroke": [0, 255, 0],
+                                     "placement": { "horizontal": "right", "vertical": "bottom",
+                                                    "offset": [-1, 0] } } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        Watchface::default()
+            .into_styled(DeclarativeStyle::new(layout).unwrap())
+            .draw(&mut display.clipped(&Rectangle::new(Point::zero(), Size::new(6, 4))))
+            .unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "RRBBBB",
+            "RRBBBB",
+            "BBGGGB",
+            "BBGGGB",
+        ]);
+    }
+
+    /// Assert that a layout with the element after a time element is rejected with the message
+    fn assert_invalid(element: &str, expected: &str) {
+        let mut json = String::<U128>::new();
+        write!(
+            json,
+            r#"{{ "elements": [ {{ "time": {{}} }}, {} ] }}"#,
+            element
+        )
+        .unwrap();
+        match Layout::from_json(&json) {
+            Err(ParseLayoutError(LayoutErrorKind::Invalid { index, message })) => {
+                assert_eq!((index, message), (1, expected))
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn battery_scale_zero() {
+        assert_invalid(
+            r#"{ "battery": { "scale": 0 } }"#,
+            "battery scale out of range",
+        );
+    }
+
+    #[test]
+    fn battery_scale_too_large() {
+        assert_invalid(
+            r#"{ "battery": { "scale": 17 } }"#,
+            "battery scale out of range",
+        );
+    }
+
+    #[test]
+    fn rectangle_size_too_large() {
+        assert_invalid(
+            r#"{ "rectangle": { "size": [4294967295, 1] } }"#,
+            "rectangle size too large",
+        );
+    }
+
+    #[test]
+    fn rectangle_stroke_width_too_large() {
+        assert_invalid(
+            r#"{ "rectangle": { "size": [1, 1], "stroke_width": 4097 } }"#,
+            "rectangle stroke width too large",
+        );
+    }
+
+    #[test]
+    fn offset_out_of_range() {
+        assert_invalid(
+            r#"{ "date": { "placement": { "offset": [2147483647, 0] } } }"#,
+            "offset out of range",
+        );
+        assert_invalid(
+            r#"{ "time": { "placement": { "offset": [0, -4097] } } }"#,
+            "offset out of range",
+        );
+    }
+
+    #[cfg(not(any(feature = "bmp", feature = "tga", feature = "qoi")))]
+    #[test]
+    fn images_not_supported() {
+        assert_invalid(
+            r#"{ "image": { "name": "logo" } }"#,
+            "images are not supported",
+        );
+    }
+
+    #[test]
+    fn new_validates_layout() {
+        let mut layout = Layout::default();
+        let battery = BatteryElement {
+            scale: 0,
+            ..BatteryElement::default()
+        };
+        layout.elements.push(Element::Battery(battery)).unwrap();
+
+        let mut buffer = [0; 32];
+        let encoded = postcard::to_slice(&layout, &mut buffer).unwrap();
+        let layout = postcard::from_bytes::<Layout>(encoded).unwrap();
+        assert!(DeclarativeStyle::<Rgb888>::new(layout).is_err());
+    }
+
+    #[test]
+    fn date_formats() {
+        let date = Date::from_values(2021, 3, 4);
+        let format = |format: DateFormat| format.format(&date);
+
+        assert_eq!(format(DateFormat::YearMonthDay), "2021-03-04");
+        assert_eq!(format(DateFormat::DayMonth), "04.03");
+        assert_eq!(format(DateFormat::DayMonthYear), "04-03-2021");
+        assert_eq!(format(DateFormat::Day), "04");
+        assert_eq!(format(DateFormat::Month), "03");
+        assert_eq!(format(DateFormat::Year), "2021");
+    }
+
+    #[test]
+    fn date_only_drawn_with_date() {
+        let layout = Layout::from_json(
+            r#"{ "elements": [ { "date": { "font": "overpass_24", "color": [255, 0, 0] } } ] }"#,
+        )
+        .unwrap();
+        let draw = |watchface: Watchface| {
+            let mut display = MockDisplay::<Rgb888>::new();
+            display.set_allow_overdraw(true);
+            display.set_allow_out_of_bounds_drawing(true);
+            watchface
+                .into_styled(DeclarativeStyle::new(layout.clone()).unwrap())
+                .draw(&mut display)
+                .unwrap();
+            display
+        };
+
+        let without_date = draw(Watchface::default());
+        let with_date = draw(
+            Watchface::build()
+                .with_date(Date::from_values(2021, 3, 4))
+                .finish(),
+        );
+        let has_red = |display: &MockDisplay<Rgb888>| {
+            let mut points = display.bounding_box().points();
+            points.any(|point| display.get_pixel(point) == Some(Rgb888::RED))
+        };
+        assert!(!has_red(&without_date));
+        assert!(has_red(&with_date));
+    }
+
+    #[test]
+    fn draw_extreme_values() {
+        let layout = Layout::from_json(
+            r#"{
+                "elements": [
+                    { "battery": { "scale": 16, "label": "inside",
+                                   "placement": { "horizontal": "right",
+                                                  "offset": [4096, -4096] } } },
+                    { "battery": { "scale": 16, "label": "beside", "orientation": "horizontal",
+                                   "placement": { "offset": [-4096, 4096] } } },
+                    { "rectangle": { "size": [4096, 4096], "fill": [255, 0, 0],
+                                     "stroke": [0, 255, 0], "stroke_width": 4096,
+                                     "placement": { "offset": [-4096, -4096] } } },
+                    { "time": { "format": "hours_minutes_seconds", "font": "overpass_72",
+                                "placement": { "vertical": "bottom", "offset": [4096, 4096] } } },
+                    { "date": { "format": "day_month_year", "font": "overpass_proportional_64",
+                                "placement": { "vertical": "top", "offset": [-4096, -4096] } } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let watchface = Watchface::build()
+            .with_time(Time::from_values(23, 59, 59))
+            .with_date(Date::from_values(9999, 12, 31))
+            .with_battery(crate::battery::StateOfCharge::from_percentage(100))
+            .with_charger(crate::battery::ChargerState::Charging)
+            .finish();
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        watchface
+            .into_styled(DeclarativeStyle::new(layout).unwrap())
+            .draw(&mut display.clipped(&Rectangle::new(Point::zero(), Size::new(64, 64))))
+            .unwrap();
+    }
+}
@@ -0,0 +1,271 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Background images and sprites behind a watchface
+//!
+//! `Decorated` wraps a styled watchface and draws images behind it. It is the `Background` of the
+//! style, so the style draws the images instead of clearing the display and anti-aliased text is
+//! blended with them.
+
+use crate::background::{Background, DrawOnBackground};
+use crate::image_asset::ImageAsset;
+use embedded_graphics::image::Image;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use embedded_layout::prelude::*;
+use heapless::consts::*;
+use heapless::Vec;
+
+/// A drawable with a background image and sprites
+///
+/// The background image is centered on the display, on top of the background color of the style
+/// which remains visible around a smaller image. Sprites are drawn on top of the background at
+/// their position, below the watchface. The wrapped drawable draws them as its background, see
+/// `DrawOnBackground`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "qoi")]
+/// # {
+/// use embedded_graphics::Drawable;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use watchface::decorated::Decorated;
+/// use watchface::image_asset::ImageAsset;
+/// use watchface::time::Time;
+/// use watchface::{SimpleWatchfaceStyle, Watchface};
+///
+/// let background = ImageAsset::from_bytes(include_bytes!("../tests/images/pattern.qoi")).unwrap();
+///
+/// let styled_watchface = Watchface::build()
+///     .with_time(Time::from_values(10, 9, 0))
+///     .into_styled(SimpleWatchfaceStyle::default());
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_out_of_bounds_drawing(true);
+/// display.set_allow_overdraw(true);
+/// Decorated::new(styled_watchface)
+///     .with_background(&background)
+///     .with_sprite(&background, Point::new(4, 4))
+///     .draw(&mut display)
+///     .unwrap();
+/// # }
+/// ```
+pub struct Decorated<'a, T> {
+    inner: T,
+    background: Option<&'a ImageAsset<'a>>,
+    sprites: Vec<(&'a ImageAsset<'a>, Point), U8>,
+}
+
+impl<'a, T> Decorated<'a, T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            background: None,
+            sprites: Vec::new(),
+        }
+    }
+
+    /// Draw an image centered behind the watchface, on top of the background color
+    pub fn with_background(mut self, image: &'a ImageAsset<'a>) -> Self {
+        self.background = Some(image);
+
+        self
+    }
+
+    /// Draw an image with the top left corner at the given position, up to 8 sprites
+    ///
+    /// # Panics
+    ///
+    /// Panics when more than 8 sprites are added.
+    pub fn with_sprite(mut self, image: &'a ImageAsset<'a>, position: Point) -> Self {
+        if self.sprites.push((image, position)).is_err() {
+            panic!("too many sprites");
+        }
+
+        self
+    }
+}
+
+impl<T, C> Background<C> for Decorated<'_, T>
+where
+    C: PixelColor + From<Rgb888>,
+{
+    fn draw_background<D>(
+        &self,
+        display_area: &Rectangle,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        target.clear(color)?;
+
+        if let Some(background) = self.background {
+            Image::new(background, Point::zero())
+                .align_to(display_area, horizontal::Center, vertical::Center)
+                .draw(&mut target.color_converted())?;
+        }
+
+        for (image, position) in &self.sprites {
+            Image::new(*image, *position).draw(&mut target.color_converted())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, C> Drawable for Decorated<'_, T>
+where
+    T: DrawOnBackground<Color = C>,
+    C: PixelColor + From<Rgb888>,
+{
+    type Color = C;
+
+    type Output = T::Output;
+
+    fn draw<D>(&self, display: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.inner.draw_on_background(display, self)
+    }
+}
+
+#[cfg(all(test, feature = "bmp"))]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::primitives::PrimitiveStyle;
+
+    /// Draws a black background and a red pixel in the top left corner
+    struct Overlay;
+
+    impl Drawable for Overlay {
+        type Color = Rgb888;
+
+        type Output = ();
+
+        fn draw<D: DrawTarget<Color = Rgb888>>(&self, display: &mut D) -> Result<(), D::Error> {
+            self.draw_on_background(display, &crate::background::PlainBackground)
+        }
+    }
+
+    impl DrawOnBackground for Overlay {
+        fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+        where
+            D: DrawTarget<Color = Rgb888>,
+            B: Background<Rgb888>,
+        {
+            background.draw_background(&display.bounding_box(), Rgb888::BLACK, display)?;
+            Rectangle::new(Point::zero(), Size::new(1, 1))
+                .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+                .draw(display)
+        }
+    }
+
+    fn draw(decorated: Decorated<'_, Overlay>) -> MockDisplay<Rgb888> {
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        decorated
+            .draw(&mut display.cropped(&Rectangle::new(Point::zero(), Size::new(6, 4))))
+            .unwrap();
+        display
+    }
+
+    #[test]
+    fn overlay_on_background() {
+        let image = ImageAsset::from_bytes(include_bytes!("../tests/images/pattern.bmp")).unwrap();
+
+        let display = draw(Decorated::new(Overlay).with_background(&image));
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "RRGBWK",
+            "KKYCMK",
+            "KWKWKK",
+            "KKKKKK",
+        ]);
+
+        let display = draw(Decorated::new(Overlay).with_sprite(&image, Point::new(2, 1)));
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "RKKKKK",
+            "KKRGBW",
+            "KKKYCM",
+            "KKWKWK",
+        ]);
+    }
+
+    #[test]
+    fn background_color_around_image() {
+        let image = ImageAsset::from_bytes(include_bytes!("../tests/images/square.bmp")).unwrap();
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        display.set_allow_overdraw(true);
+        let mut target = display.cropped(&Rectangle::new(Point::zero(), Size::new(6, 4)));
+        target
+            .fill_solid(&target.bounding_box(), Rgb888::YELLOW)
+            .unwrap();
+        Decorated::new(Overlay)
+            .with_background(&image)
+            .draw(&mut target)
+            .unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "RRGBKK",
+            "KCMYKK",
+            "KWKWKK",
+            "KKKKKK",
+        ]);
+    }
+
+    #[cfg(all(feature = "qoi", feature = "std"))]
+    #[test]
+    fn anti_aliased_text_on_image() {
+        use crate::framebuffer::Framebuffer;
+        use crate::time::Time;
+        use crate::{NamedStyle, StyleName, Watchface};
+
+        let image = ImageAsset::from_bytes(include_bytes!("../tests/images/blue.qoi")).unwrap();
+        let blue = Rgb888::new(0, 0, 160);
+
+        let mut framebuffer = Framebuffer::new(Size::new(240, 240), Rgb888::BLACK);
+        let styled_watchface = Watchface::build()
+            .with_time(Time::from_values(10, 8, 0))
+            .into_styled(NamedStyle::new(StyleName::SimpleAntiAliased));
+        Decorated::new(styled_watchface)
+            .with_background(&image)
+            .draw(&mut framebuffer)
+            .unwrap();
+
+        // The edges of the white digits blend with the blue image, not with the black background
+        // color of the style
+        let pixels = framebuffer.pixels();
+        assert!(!pixels.contains(&Rgb888::BLACK));
+        assert!(pixels
+            .iter()
+            .any(|pixel| *pixel != blue && *pixel != Rgb888::WHITE && pixel.b() > 160));
+        assert!(!pixels
+            .iter()
+            .any(|pixel| pixel.r() == pixel.g() && pixel.g() == pixel.b() && pixel.r() < 255));
+    }
+}
@@ -15,16 +15,34 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+//! Fonts for drawing large digits
+//!
+//! `OVERPASS_NUMBERS_FONT` is converted by hand from `data/overpass-numbers.xcf`. The
+//! `OVERPASS_DIGITS_*` fonts are rasterized from `data/overpass-mono-bold.otf` by the build
+//! script. They contain the characters of `DIGIT_CHARACTERS`, other characters are drawn as `?`.
+//! Sizes besides 24, 40 and 72 pixels are added with the `WATCHFACE_FONT_SIZES` environment
+//! variable, see the README.
+//! `OVERPASS_DIGITS_AA_*` fonts contain the same characters with anti-aliased edges and
+//! `OVERPASS_DIGITS_PROPORTIONAL_*` fonts contain them with proportional widths and kerning.
+
+use crate::anti_aliased_font::{AntiAliasedFont, BitsPerPixel};
+use crate::proportional_font::{ProportionalFont, ProportionalGlyph};
 use embedded_graphics::{
     geometry::Size,
     image::ImageRaw,
-    mono_font::{mapping::GlyphMapping, DecorationDimensions, MonoFont},
+    mono_font::{
+        mapping::{GlyphMapping, StrGlyphMapping},
+        DecorationDimensions, MonoFont,
+    },
 };
 
-const CHARS_PER_ROW: u32 = 16;
+include!(concat!(env!("OUT_DIR"), "/fonts.rs"));
+
+const CHARS_PER_ROW: u32 = 10;
 const CHAR_WIDTH: u32 = 40;
 const CHAR_HEIGHT: u32 = 52;
 
+/// Overpass Mono Bold digits with a size of 40x52 pixels
 pub const OVERPASS_NUMBERS_FONT: MonoFont = MonoFont {
     image: ImageRaw::new_binary(
         include_bytes!("../data/overpass-numbers.raw"),
@@ -38,16 +56,58 @@ pub const OVERPASS_NUMBERS_FONT: MonoFont = MonoFont {
     strikethrough: DecorationDimensions::new(26, 1),
 };
 
+include!("font_characters.rs");
+
+/// Mapping of the characters in `overpass-numbers.raw`, which are in the order of
+/// `DIGIT_CHARACTERS`
+///
+/// Characters which are not available are mapped to a question mark.
 struct ClockDigitMapping {}
 
 impl GlyphMapping for ClockDigitMapping {
     fn index(&self, c: char) -> usize {
-        if c >= '0' || c <= '9' {
-            c as usize - '0' as usize
-        } else if c == ':' {
-            10
-        } else {
-            15
-        }
+        let replacement = DIGIT_CHARACTERS.chars().count() - 1;
+        DIGIT_CHARACTERS
+            .chars()
+            .position(|character| character == c)
+            .unwrap_or(replacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::mono_font::MonoTextStyle;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::prelude::*;
+    use embedded_graphics::text::Text;
+
+    #[test]
+    fn clock_digit_mapping() {
+        let mapping = ClockDigitMapping {};
+
+        assert_eq!(mapping.index('0'), 0);
+        assert_eq!(mapping.index('9'), 9);
+        assert_eq!(mapping.index(':'), 10);
+        assert_eq!(mapping.index('%'), 14);
+        assert_eq!(mapping.index('M'), 17);
+        assert_eq!(mapping.index('/'), 18);
+        assert_eq!(mapping.index('a'), 18);
+    }
+
+    #[test]
+    fn generated_font_replacement_character() {
+        let draw = |text| {
+            let mut display = MockDisplay::<BinaryColor>::new();
+            let style = MonoTextStyle::new(&OVERPASS_DIGITS_24, BinaryColor::On);
+            Text::new(text, Point::new(0, 20), style)
+                .draw(&mut display)
+                .unwrap();
+            display
+        };
+
+        assert_eq!(draw("/"), draw("?"));
+        assert_ne!(draw("8"), draw("?"));
     }
 }
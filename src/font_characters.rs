@@ -0,0 +1,22 @@
+/* SPDX-FileCopyrightText: © 2020 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+// Shared with the build script, which rasterizes these characters into the generated fonts
+
+/// Characters of the digit fonts in glyph order, the last character is drawn for unknown
+/// characters
+pub const DIGIT_CHARACTERS: &str = "0123456789: -.%APM?";
@@ -0,0 +1,186 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! In-memory draw target
+//!
+//! A `Framebuffer` stores all pixels in memory, so that a watchface can be drawn without a
+//! display. This is useful for rendering screenshots and for testing.
+
+use core::fmt;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use std::vec::Vec;
+
+/// Error returned when a buffer for every pixel of a size doesn't fit in memory
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TooLargeError {
+    pub size: Size,
+}
+
+impl fmt::Display for TooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} pixels don't fit in memory",
+            self.size.width, self.size.height
+        )
+    }
+}
+
+impl std::error::Error for TooLargeError {}
+
+/// Allocate a buffer with a value for every pixel of the given size
+pub(crate) fn pixel_buffer<T: Clone>(size: Size, value: T) -> Result<Vec<T>, TooLargeError> {
+    let length = (size.width as usize)
+        .checked_mul(size.height as usize)
+        .ok_or(TooLargeError { size })?;
+
+    let mut buffer = Vec::new();
+    buffer
+        .try_reserve_exact(length)
+        .map_err(|_| TooLargeError { size })?;
+    buffer.resize(length, value);
+
+    Ok(buffer)
+}
+
+/// A draw target that stores the pixels in memory
+///
+/// Pixels that are drawn outside of the framebuffer are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+/// use watchface::framebuffer::Framebuffer;
+///
+/// let mut framebuffer = Framebuffer::new(Size::new(240, 240), Rgb888::BLACK);
+///
+/// Rectangle::new(Point::new(10, 10), Size::new(2, 2))
+///     .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+///     .draw(&mut framebuffer)
+///     .unwrap();
+///
+/// assert_eq!(framebuffer.pixel(Point::new(11, 11)), Some(Rgb888::RED));
+/// assert_eq!(framebuffer.pixel(Point::new(12, 12)), Some(Rgb888::BLACK));
+/// assert_eq!(framebuffer.pixel(Point::new(240, 0)), None);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Framebuffer<C> {
+    size: Size,
+    pixels: Vec<C>,
+}
+
+impl<C> Framebuffer<C>
+where
+    C: PixelColor,
+{
+    /// Create a framebuffer with all pixels set to the background color
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixels don't fit in memory, use `try_new` to handle this.
+    pub fn new(size: Size, background: C) -> Self {
+        Self::try_new(size, background).unwrap()
+    }
+
+    /// Create a framebuffer with all pixels set to the background color, fails when the pixels
+    /// don't fit in memory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::Rgb888;
+    /// use embedded_graphics::prelude::*;
+    /// use watchface::framebuffer::Framebuffer;
+    ///
+    /// assert!(Framebuffer::try_new(Size::new(240, 240), Rgb888::BLACK).is_ok());
+    /// assert!(Framebuffer::try_new(Size::new(u32::MAX, u32::MAX), Rgb888::BLACK).is_err());
+    /// ```
+    pub fn try_new(size: Size, background: C) -> Result<Self, TooLargeError> {
+        Ok(Self {
+            size,
+            pixels: pixel_buffer(size, background)?,
+        })
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        if point.x >= 0
+            && point.y >= 0
+            && (point.x as u32) < self.size.width
+            && (point.y as u32) < self.size.height
+        {
+            Some(point.y as usize * self.size.width as usize + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color of a pixel, or `None` if the point is outside of the framebuffer
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        self.index(point).map(|index| self.pixels[index])
+    }
+
+    /// Returns all pixels, row by row
+    pub fn pixels(&self) -> &[C] {
+        &self.pixels
+    }
+
+    /// Returns the pixels as 8-bit RGB values, row by row
+    pub fn to_rgb_bytes(&self) -> Vec<u8>
+    where
+        C: Into<Rgb888>,
+    {
+        self.pixels
+            .iter()
+            .flat_map(|&pixel| {
+                let pixel: Rgb888 = pixel.into();
+                [pixel.r(), pixel.g(), pixel.b()]
+            })
+            .collect()
+    }
+}
+
+impl<C> DrawTarget for Framebuffer<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(index) = self.index(point) {
+                self.pixels[index] = color;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> OriginDimensions for Framebuffer<C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
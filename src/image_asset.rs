@@ -0,0 +1,243 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Images in BMP, TGA or QOI format
+//!
+//! Each format is decoded by a separate crate, enabled with the `bmp`, `tga` and `qoi` features.
+//! The image data is borrowed, so it can be embedded using `include_bytes!` or loaded at runtime.
+//! Images are decoded while drawing, without storing the pixels.
+
+use core::fmt;
+use embedded_graphics::image::ImageDrawable;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// File format of an image
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ImageFormat {
+    Bmp,
+    Tga,
+    Qoi,
+}
+
+/// Error returned when image data can't be used
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ImageError {
+    /// The data is not in one of the enabled formats
+    UnknownFormat,
+    /// The data is not a valid image, or uses an unsupported variant of the format
+    Invalid(ImageFormat),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::UnknownFormat => write!(f, "unknown image format"),
+            ImageError::Invalid(format) => write!(f, "invalid {:?} image", format),
+        }
+    }
+}
+
+/// A decodable image, with the pixels converted to `Rgb888`
+///
+/// The image can be drawn to a display of another color type using
+/// `DrawTargetExt::color_converted`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "bmp")]
+/// # {
+/// use embedded_graphics::image::Image;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use watchface::image_asset::ImageAsset;
+///
+/// let asset = ImageAsset::from_bytes(include_bytes!("../tests/images/pattern.bmp")).unwrap();
+/// assert_eq!(asset.size(), Size::new(4, 3));
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// Image::new(&asset, Point::zero()).draw(&mut display).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum ImageAsset<'a> {
+    #[cfg(feature = "bmp")]
+    Bmp(tinybmp::DynamicBmp<'a, Rgb888>),
+    #[cfg(feature = "tga")]
+    Tga(tinytga::DynamicTga<'a, Rgb888>),
+    #[cfg(feature = "qoi")]
+    Qoi(tinyqoi::Qoi<'a>),
+}
+
+impl<'a> ImageAsset<'a> {
+    /// Parse an image, detecting the format from the data
+    ///
+    /// BMP and QOI files are recognized by their signature. TGA files have no signature, so any
+    /// other data is parsed as TGA when that format is enabled.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, ImageError> {
+        if data.starts_with(b"BM") {
+            Self::from_format(data, ImageFormat::Bmp)
+        } else if data.starts_with(b"qoif") {
+            Self::from_format(data, ImageFormat::Qoi)
+        } else if cfg!(feature = "tga") {
+            Self::from_format(data, ImageFormat::Tga)
+        } else {
+            Err(ImageError::UnknownFormat)
+        }
+    }
+
+    /// Parse an image in the given format
+    pub fn from_format(data: &'a [u8], format: ImageFormat) -> Result<Self, ImageError> {
+        match format {
+            #[cfg(feature = "bmp")]
+            ImageFormat::Bmp => tinybmp::DynamicBmp::from_slice(data)
+                .map(ImageAsset::Bmp)
+                .map_err(|_| ImageError::Invalid(format)),
+            #[cfg(feature = "tga")]
+            ImageFormat::Tga => tinytga::DynamicTga::from_slice(data)
+                .map(ImageAsset::Tga)
+                .map_err(|_| ImageError::Invalid(format)),
+            #[cfg(feature = "qoi")]
+            ImageFormat::Qoi => tinyqoi::Qoi::new(data)
+                .map(ImageAsset::Qoi)
+                .map_err(|_| ImageError::Invalid(format)),
+            #[allow(unreachable_patterns)]
+            _ => Err(ImageError::UnknownFormat),
+        }
+    }
+
+    /// Format of the image data
+    pub fn format(&self) -> ImageFormat {
+        match self {
+            #[cfg(feature = "bmp")]
+            ImageAsset::Bmp(_) => ImageFormat::Bmp,
+            #[cfg(feature = "tga")]
+            ImageAsset::Tga(_) => ImageFormat::Tga,
+            #[cfg(feature = "qoi")]
+            ImageAsset::Qoi(_) => ImageFormat::Qoi,
+        }
+    }
+}
+
+impl ImageDrawable for ImageAsset<'_> {
+    type Color = Rgb888;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        match self {
+            #[cfg(feature = "bmp")]
+            ImageAsset::Bmp(image) => image.draw(target),
+            #[cfg(feature = "tga")]
+            ImageAsset::Tga(image) => image.draw(target),
+            #[cfg(feature = "qoi")]
+            ImageAsset::Qoi(image) => image.draw(target),
+        }
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb888>,
+    {
+        match self {
+            #[cfg(feature = "bmp")]
+            ImageAsset::Bmp(image) => image.draw_sub_image(target, area),
+            #[cfg(feature = "tga")]
+            ImageAsset::Tga(image) => image.draw_sub_image(target, area),
+            #[cfg(feature = "qoi")]
+            ImageAsset::Qoi(image) => image.draw_sub_image(target, area),
+        }
+    }
+}
+
+impl OriginDimensions for ImageAsset<'_> {
+    fn size(&self) -> Size {
+        match self {
+            #[cfg(feature = "bmp")]
+            ImageAsset::Bmp(image) => image.size(),
+            #[cfg(feature = "tga")]
+            ImageAsset::Tga(image) => image.size(),
+            #[cfg(feature = "qoi")]
+            ImageAsset::Qoi(image) => image.size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::image::Image;
+    use embedded_graphics::mock_display::MockDisplay;
+
+    const PATTERN: [&str; 3] = ["RGBW", "KYCM", "WKWK"];
+
+    fn assert_pattern(data: &[u8], format: ImageFormat) {
+        let asset = ImageAsset::from_bytes(data).unwrap();
+        assert_eq!(asset.format(), format);
+        assert_eq!(asset.size(), Size::new(4, 3));
+
+        let mut display = MockDisplay::<Rgb888>::new();
+        Image::new(&asset, Point::zero())
+            .draw(&mut display)
+            .unwrap();
+        display.assert_pattern(&PATTERN);
+    }
+
+    #[cfg(feature = "bmp")]
+    #[test]
+    fn bmp() {
+        assert_pattern(
+            include_bytes!("../tests/images/pattern.bmp"),
+            ImageFormat::Bmp,
+        );
+    }
+
+    #[cfg(feature = "tga")]
+    #[test]
+    fn tga() {
+        assert_pattern(
+            include_bytes!("../tests/images/pattern.tga"),
+            ImageFormat::Tga,
+        );
+    }
+
+    #[cfg(feature = "qoi")]
+    #[test]
+    fn qoi() {
+        assert_pattern(
+            include_bytes!("../tests/images/pattern.qoi"),
+            ImageFormat::Qoi,
+        );
+    }
+
+    #[cfg(all(feature = "bmp", feature = "qoi"))]
+    #[test]
+    fn invalid_data() {
+        assert_eq!(
+            ImageAsset::from_format(b"BM", ImageFormat::Bmp).unwrap_err(),
+            ImageError::Invalid(ImageFormat::Bmp)
+        );
+        assert_eq!(
+            ImageAsset::from_bytes(b"qoif").unwrap_err(),
+            ImageError::Invalid(ImageFormat::Qoi)
+        );
+    }
+}
@@ -47,23 +47,112 @@
 //! styled_watchface.draw(&mut display);
 //! ```
 //!
+//! # Features
+//!
+//! * `std` (default): use the system clock through chrono, needed for the simulator
+//! * `chrono`: conversions from chrono date and time types
+//! * `time`: conversions from the `time` crate date and time types
+//! * `rtcc`: read the time from a real-time clock implementing the `rtcc` traits
+//! * `render`: the `watchface-render` binary
+//! * `serde`: serialization of the watchface data, also without `std` and alloc
+//! * `declarative`: the `declarative` module, for watchface layouts loaded from JSON or a
+//!   binary format
+//! * `bmp`, `tga` and `qoi`: the `image_asset` module for images in these formats, and the
+//!   `decorated` module for drawing them behind a watchface
+//!
+//! # Serialization
+//!
+//! With the `serde` feature, `Watchface` and its data can be serialized. The schema is:
+//!
+//! * `Watchface`: a struct with the optional fields `time`, `date`, `charger`, `battery` and
+//!   `notifications`, missing fields are empty
+//! * `Time`: a `"HH:MM:SS"` string in human-readable formats like JSON, otherwise a tuple of
+//!   hours, minutes and seconds. Seconds are optional when deserializing a string.
+//! * `Date`: a `"YYYY-MM-DD"` string in human-readable formats, otherwise a tuple of year, month
+//!   and day
+//! * `StateOfCharge`: the level as a number (0..255)
+//! * `notifications`: the number of unread notifications (0..255)
+//! * `ChargerState`: the name in snake case, like `"over_temperature"`. Binary formats use the
+//!   index of the variant, new variants are only added at the end.
+//!
+//! ```json
+//! {
+//!   "time": "10:09:30",
+//!   "date": "2021-03-04",
+//!   "charger": "charging",
+//!   "battery": 191,
+//!   "notifications": 3
+//! }
+//! ```
+//!
 //! # Simulator
 //!
-//! A simulator is available for testing a watchface on a desktop. Run the example using:
+//! A simulator is available for testing a watchface on a desktop. The style and watchface data
+//! can be changed using the keyboard and mouse, run it with `--help` to list the controls. Run
+//! the example using:
 //! ```bash
 //! cargo run --example simulator
 //! ```
+//!
+//! The simulator plays a scenario script with changes to the watchface data, see the `scenario`
+//! module. For example, to watch a full day in a minute:
+//! ```bash
+//! cargo run --example simulator -- --scenario examples/scenarios/day.scenario --speed 1440
+//! ```
+//!
+//! Without a graphical display, the watchface can be shown in a terminal with 24-bit color
+//! support:
+//! ```bash
+//! cargo run --example terminal
+//! ```
+//!
+//! # Screenshots
+//!
+//! A style can also be rendered to a PNG file, without a display:
+//! ```bash
+//! cargo run --features render --bin watchface-render -- --style word-clock --time 10:09
+//! ```
+
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod anti_aliased_font;
+pub mod background;
 pub mod battery;
 pub mod battery_icon;
-mod font;
+mod binary_clock_watchface;
+pub mod date;
+#[cfg(feature = "declarative")]
+pub mod declarative;
+#[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+pub mod decorated;
+pub mod font;
+#[cfg(feature = "std")]
+pub mod framebuffer;
+#[cfg(any(feature = "bmp", feature = "tga", feature = "qoi"))]
+pub mod image_asset;
+mod named_style;
+pub mod phrase;
+#[cfg(feature = "std")]
+pub mod profiler;
+pub mod proportional_font;
+#[cfg(feature = "std")]
+pub mod scenario;
+pub mod seven_segment;
 mod simple_watchface;
 mod styled;
+#[cfg(feature = "std")]
+pub mod terminal;
 mod textual_time_watchface;
+pub mod theme;
 pub mod time;
 mod watchface_data;
+mod word_clock_watchface;
 
+pub use binary_clock_watchface::{BinaryClockWatchfaceStyle, BinaryMode};
+pub use named_style::{NamedStyle, ParseStyleNameError, StyleName};
 pub use simple_watchface::SimpleWatchfaceStyle;
 pub use textual_time_watchface::TextualTimeWatchfaceStyle;
 pub use watchface_data::Watchface;
 pub use watchface_data::WatchfaceBuilder;
+pub use word_clock_watchface::WordClockWatchfaceStyle;
@@ -0,0 +1,196 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::background::{Background, DrawOnBackground, PlainBackground};
+use crate::phrase::Language;
+use crate::styled::Styled;
+use crate::{
+    BinaryClockWatchfaceStyle, BinaryMode, SimpleWatchfaceStyle, TextualTimeWatchfaceStyle,
+    Watchface, WordClockWatchfaceStyle,
+};
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+use embedded_graphics::pixelcolor::{Rgb888, RgbColor};
+use embedded_graphics::prelude::*;
+
+/// Name of a built-in style with its default settings
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum StyleName {
+    /// `SimpleWatchfaceStyle`
+    Simple,
+    /// `SimpleWatchfaceStyle` with anti-aliasing
+    SimpleAntiAliased,
+    /// `TextualTimeWatchfaceStyle`
+    Textual,
+    /// `WordClockWatchfaceStyle` in Dutch
+    WordClock,
+    /// `WordClockWatchfaceStyle` in English
+    WordClockEnglish,
+    /// `BinaryClockWatchfaceStyle`
+    Binary,
+    /// `BinaryClockWatchfaceStyle` in BCD mode
+    Bcd,
+}
+
+impl StyleName {
+    /// All built-in styles
+    pub const ALL: [StyleName; 7] = [
+        StyleName::Simple,
+        StyleName::SimpleAntiAliased,
+        StyleName::Textual,
+        StyleName::WordClock,
+        StyleName::WordClockEnglish,
+        StyleName::Binary,
+        StyleName::Bcd,
+    ];
+
+    /// Returns the name in kebab case, like `word-clock-english`
+    pub fn name(self) -> &'static str {
+        match self {
+            StyleName::Simple => "simple",
+            StyleName::SimpleAntiAliased => "simple-anti-aliased",
+            StyleName::Textual => "textual",
+            StyleName::WordClock => "word-clock",
+            StyleName::WordClockEnglish => "word-clock-english",
+            StyleName::Binary => "binary",
+            StyleName::Bcd => "bcd",
+        }
+    }
+}
+
+impl fmt::Display for StyleName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing a `StyleName` fails
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ParseStyleNameError;
+
+/// Parse a style from its kebab case name
+///
+/// # Examples
+/// ```
+/// use watchface::StyleName;
+/// assert_eq!("word-clock".parse(), Ok(StyleName::WordClock));
+/// assert!("analog".parse::<StyleName>().is_err());
+/// ```
+impl FromStr for StyleName {
+    type Err = ParseStyleNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StyleName::ALL
+            .iter()
+            .copied()
+            .find(|style| style.name() == s)
+            .ok_or(ParseStyleNameError)
+    }
+}
+
+/// Style that draws the built-in style with the given name
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::Drawable;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use watchface::time::Time;
+/// use watchface::{NamedStyle, Watchface};
+///
+/// let styled_watchface = Watchface::build()
+///     .with_time(Time::from_values(10, 9, 0))
+///     .into_styled(NamedStyle::new("bcd".parse().unwrap()));
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_out_of_bounds_drawing(true);
+/// display.set_allow_overdraw(true);
+/// styled_watchface.draw(&mut display).unwrap();
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct NamedStyle<C> {
+    name: StyleName,
+    _phantom_data: PhantomData<C>,
+}
+
+impl<C> NamedStyle<C> {
+    pub fn new(name: StyleName) -> Self {
+        Self {
+            name,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Name of the drawn style
+    pub fn name(&self) -> StyleName {
+        self.name
+    }
+}
+
+impl<C> Drawable for Styled<Watchface, NamedStyle<C>>
+where
+    C: RgbColor + From<Rgb888> + Default,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, NamedStyle<C>>
+where
+    C: RgbColor + From<Rgb888> + Default,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
+        let watchface = self.watchface.clone();
+        match self.style.name {
+            StyleName::Simple => watchface
+                .into_styled(SimpleWatchfaceStyle::default())
+                .draw_on_background(display, background),
+            StyleName::SimpleAntiAliased => watchface
+                .into_styled(SimpleWatchfaceStyle::default().with_anti_aliasing(true))
+                .draw_on_background(display, background),
+            StyleName::Textual => watchface
+                .into_styled(TextualTimeWatchfaceStyle::default())
+                .draw_on_background(display, background),
+            StyleName::WordClock => watchface
+                .into_styled(WordClockWatchfaceStyle::default())
+                .draw_on_background(display, background),
+            StyleName::WordClockEnglish => watchface
+                .into_styled(WordClockWatchfaceStyle::default().with_language(Language::English))
+                .draw_on_background(display, background),
+            StyleName::Binary => watchface
+                .into_styled(BinaryClockWatchfaceStyle::default())
+                .draw_on_background(display, background),
+            StyleName::Bcd => watchface
+                .into_styled(BinaryClockWatchfaceStyle::default().with_mode(BinaryMode::Bcd))
+                .draw_on_background(display, background),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Time as spoken words
+//!
+//! Converts a time into the words that are used to tell the time in a certain language, such as
+//! "kwart over drie" or "twenty five to four".
+
+use crate::time::{Granularity, RoundingMode, Time};
+use heapless::consts::*;
+use heapless::Vec;
+
+/// Language used for telling the time
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Language {
+    #[default]
+    Dutch,
+    English,
+}
+
+/// A single word of a spoken time
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Word {
+    It,
+    Is,
+    Five,
+    Ten,
+    Quarter,
+    Twenty,
+    Half,
+    Past,
+    To,
+    OClock,
+    /// An hour on the 12-hour clock, 0 and 12 are both twelve
+    Hour(u8),
+}
+
+impl Word {
+    /// Returns the lowercase text of this word
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::phrase::{Language, Word};
+    /// assert_eq!(Word::Quarter.text(Language::Dutch), "kwart");
+    /// assert_eq!(Word::Hour(13).text(Language::English), "one");
+    /// ```
+    pub fn text(self, language: Language) -> &'static str {
+        match language {
+            Language::Dutch => match self {
+                Word::It => "het",
+                Word::Is => "is",
+                Word::Five => "vijf",
+                Word::Ten => "tien",
+                Word::Quarter => "kwart",
+                Word::Twenty => "twintig",
+                Word::Half => "half",
+                Word::Past => "over",
+                Word::To => "voor",
+                Word::OClock => "uur",
+                Word::Hour(hours) => match hours % 12 {
+                    0 => "twaalf",
+                    1 => "één",
+                    2 => "twee",
+                    3 => "drie",
+                    4 => "vier",
+                    5 => "vijf",
+                    6 => "zes",
+                    7 => "zeven",
+                    8 => "acht",
+                    9 => "negen",
+                    10 => "tien",
+                    _ => "elf",
+                },
+            },
+            Language::English => match self {
+                Word::It => "it",
+                Word::Is => "is",
+                Word::Five => "five",
+                Word::Ten => "ten",
+                Word::Quarter => "quarter",
+                Word::Twenty => "twenty",
+                Word::Half => "half",
+                Word::Past => "past",
+                Word::To => "to",
+                Word::OClock => "o'clock",
+                Word::Hour(hours) => match hours % 12 {
+                    0 => "twelve",
+                    1 => "one",
+                    2 => "two",
+                    3 => "three",
+                    4 => "four",
+                    5 => "five",
+                    6 => "six",
+                    7 => "seven",
+                    8 => "eight",
+                    9 => "nine",
+                    10 => "ten",
+                    _ => "eleven",
+                },
+            },
+        }
+    }
+}
+
+/// Returns the words for a time, without the leading "it is"
+///
+/// The time is first rounded to the nearest five minutes.
+///
+/// # Examples
+/// ```
+/// use watchface::phrase::{time_to_words, Language, Word};
+/// use watchface::time::Time;
+///
+/// let words = time_to_words(&Time::from_values(15, 19, 0), Language::Dutch);
+/// assert_eq!(words, [Word::Ten, Word::To, Word::Half, Word::Hour(4)]);
+///
+/// let words = time_to_words(&Time::from_values(15, 19, 0), Language::English);
+/// assert_eq!(words, [Word::Twenty, Word::Past, Word::Hour(3)]);
+/// ```
+pub fn time_to_words(time: &Time, language: Language) -> Vec<Word, U5> {
+    let rounded_time = time.round_to(Granularity::FiveMinutes, RoundingMode::Nearest);
+    let hour = Word::Hour(rounded_time.hours_local() % 12);
+    let next_hour = Word::Hour((rounded_time.hours_local() + 1) % 12);
+
+    let words: &[Word] = match (language, rounded_time.minutes_local()) {
+        (Language::Dutch, 0) => &[hour, Word::OClock],
+        (Language::Dutch, 5) => &[Word::Five, Word::Past, hour],
+        (Language::Dutch, 10) => &[Word::Ten, Word::Past, hour],
+        (Language::Dutch, 15) => &[Word::Quarter, Word::Past, hour],
+        (Language::Dutch, 20) => &[Word::Ten, Word::To, Word::Half, next_hour],
+        (Language::Dutch, 25) => &[Word::Five, Word::To, Word::Half, next_hour],
+        (Language::Dutch, 30) => &[Word::Half, next_hour],
+        (Language::Dutch, 35) => &[Word::Five, Word::Past, Word::Half, next_hour],
+        (Language::Dutch, 40) => &[Word::Ten, Word::Past, Word::Half, next_hour],
+        (Language::Dutch, 45) => &[Word::Quarter, Word::To, next_hour],
+        (Language::Dutch, 50) => &[Word::Ten, Word::To, next_hour],
+        (Language::Dutch, _) => &[Word::Five, Word::To, next_hour],
+        (Language::English, 0) => &[hour, Word::OClock],
+        (Language::English, 5) => &[Word::Five, Word::Past, hour],
+        (Language::English, 10) => &[Word::Ten, Word::Past, hour],
+        (Language::English, 15) => &[Word::Quarter, Word::Past, hour],
+        (Language::English, 20) => &[Word::Twenty, Word::Past, hour],
+        (Language::English, 25) => &[Word::Twenty, Word::Five, Word::Past, hour],
+        (Language::English, 30) => &[Word::Half, Word::Past, hour],
+        (Language::English, 35) => &[Word::Twenty, Word::Five, Word::To, next_hour],
+        (Language::English, 40) => &[Word::Twenty, Word::To, next_hour],
+        (Language::English, 45) => &[Word::Quarter, Word::To, next_hour],
+        (Language::English, 50) => &[Word::Ten, Word::To, next_hour],
+        (Language::English, _) => &[Word::Five, Word::To, next_hour],
+    };
+
+    Vec::from_slice(words).unwrap()
+}
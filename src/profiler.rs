@@ -0,0 +1,337 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Draw cost profiling
+//!
+//! Displays are often connected using a slow bus like SPI, so every pixel that is written costs
+//! time and energy. The `DrawProfiler` wraps a draw target and reports how many pixels a drawable
+//! writes, how often pixels are overwritten and which areas are touched.
+
+use crate::framebuffer::{pixel_buffer, TooLargeError};
+use core::fmt;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+use std::vec::Vec;
+
+/// Draw cost of a single `draw` call
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DrawReport {
+    /// Number of pixels written, including pixels that are written multiple times
+    pub pixels: u32,
+    /// Number of distinct pixels written
+    pub unique_pixels: u32,
+    /// Number of pixels that are written more than once
+    pub overdrawn_pixels: u32,
+    /// Highest number of times a single pixel is written
+    pub max_overdraw: u32,
+    /// Number of pixels written outside of the display
+    pub outside_pixels: u32,
+    /// Number of calls to `DrawTarget::draw_iter`
+    pub draw_iter_calls: u32,
+    /// Number of calls to `DrawTarget::fill_contiguous`
+    pub fill_contiguous_calls: u32,
+    /// Number of calls to `DrawTarget::fill_solid`
+    pub fill_solid_calls: u32,
+    /// Number of calls to `DrawTarget::clear`
+    pub clear_calls: u32,
+    /// Area touched by each call to the draw target
+    pub bounding_boxes: Vec<Rectangle>,
+}
+
+impl DrawReport {
+    /// Returns the area that contains all touched areas
+    pub fn bounding_box(&self) -> Option<Rectangle> {
+        self.bounding_boxes
+            .iter()
+            .filter_map(|area| Some((area.top_left, area.bottom_right()?)))
+            .reduce(
+                |(top_left_1, bottom_right_1), (top_left_2, bottom_right_2)| {
+                    (
+                        top_left_1.component_min(top_left_2),
+                        bottom_right_1.component_max(bottom_right_2),
+                    )
+                },
+            )
+            .map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+    }
+}
+
+impl fmt::Display for DrawReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "pixels written: {} ({} unique, {} overdrawn, at most {} times, {} outside)",
+            self.pixels,
+            self.unique_pixels,
+            self.overdrawn_pixels,
+            self.max_overdraw,
+            self.outside_pixels
+        )?;
+        writeln!(
+            f,
+            "calls: {} draw_iter, {} fill_contiguous, {} fill_solid, {} clear",
+            self.draw_iter_calls,
+            self.fill_contiguous_calls,
+            self.fill_solid_calls,
+            self.clear_calls
+        )?;
+        match self.bounding_box() {
+            Some(area) => write!(
+                f,
+                "bounding box: {}x{} at ({}, {})",
+                area.size.width, area.size.height, area.top_left.x, area.top_left.y
+            ),
+            None => write!(f, "bounding box: none"),
+        }
+    }
+}
+
+/// Collects the draw cost while drawing
+struct Recorder {
+    display_area: Rectangle,
+    writes: Vec<u32>,
+    report: DrawReport,
+}
+
+impl Recorder {
+    fn new(display_area: Rectangle) -> Result<Self, TooLargeError> {
+        Ok(Self {
+            display_area,
+            writes: pixel_buffer(display_area.size, 0)?,
+            report: DrawReport::default(),
+        })
+    }
+
+    fn record_pixel(&mut self, point: Point) {
+        self.report.pixels += 1;
+        if self.display_area.contains(point) {
+            let offset = point - self.display_area.top_left;
+            let index =
+                offset.y as usize * self.display_area.size.width as usize + offset.x as usize;
+            self.writes[index] += 1;
+        } else {
+            self.report.outside_pixels += 1;
+        }
+    }
+
+    fn record_area(&mut self, area: &Rectangle) {
+        for point in area.points() {
+            self.record_pixel(point);
+        }
+        self.report.bounding_boxes.push(*area);
+    }
+
+    fn finish(&mut self) -> DrawReport {
+        let mut report = core::mem::take(&mut self.report);
+        for writes in self.writes.iter_mut() {
+            if *writes > 0 {
+                report.unique_pixels += 1;
+            }
+            if *writes > 1 {
+                report.overdrawn_pixels += 1;
+            }
+            report.max_overdraw = report.max_overdraw.max(*writes);
+            *writes = 0;
+        }
+        report
+    }
+}
+
+/// A draw target wrapper that measures the draw cost
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use watchface::framebuffer::Framebuffer;
+/// use watchface::profiler::DrawProfiler;
+/// use watchface::time::Time;
+/// use watchface::{SimpleWatchfaceStyle, Watchface};
+///
+/// let framebuffer = Framebuffer::new(Size::new(240, 240), Rgb888::BLACK);
+/// let mut profiler = DrawProfiler::new(framebuffer);
+///
+/// let watchface = Watchface::build()
+///     .with_time(Time::from_values(10, 9, 0))
+///     .into_styled(SimpleWatchfaceStyle::default());
+///
+/// let report = profiler.profile(&watchface).unwrap();
+/// assert_eq!(report.clear_calls, 1);
+/// assert_eq!(report.unique_pixels, 240 * 240);
+/// println!("{}", report);
+/// ```
+pub struct DrawProfiler<D> {
+    target: D,
+    recorder: Recorder,
+}
+
+impl<D> DrawProfiler<D>
+where
+    D: DrawTarget,
+{
+    /// Wrap a draw target
+    ///
+    /// # Panics
+    ///
+    /// Panics if a counter for every pixel of the target doesn't fit in memory, use `try_new` to
+    /// handle this.
+    pub fn new(target: D) -> Self {
+        Self::try_new(target).unwrap()
+    }
+
+    /// Wrap a draw target, fails when a counter for every pixel of the target doesn't fit in
+    /// memory
+    pub fn try_new(target: D) -> Result<Self, TooLargeError> {
+        let recorder = Recorder::new(target.bounding_box())?;
+        Ok(Self { target, recorder })
+    }
+
+    /// Draw a drawable and return its draw cost
+    pub fn profile<T>(&mut self, drawable: &T) -> Result<DrawReport, D::Error>
+    where
+        T: Drawable<Color = D::Color>,
+    {
+        self.recorder.finish();
+        drawable.draw(self)?;
+        Ok(self.recorder.finish())
+    }
+
+    /// Returns the draw cost since the last report and starts a new report
+    pub fn take_report(&mut self) -> DrawReport {
+        self.recorder.finish()
+    }
+
+    /// Returns the wrapped draw target
+    pub fn into_inner(self) -> D {
+        self.target
+    }
+}
+
+impl<D> DrawTarget for DrawProfiler<D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let recorder = &mut self.recorder;
+        recorder.report.draw_iter_calls += 1;
+
+        let mut corners: Option<(Point, Point)> = None;
+        let result = self
+            .target
+            .draw_iter(pixels.into_iter().inspect(|Pixel(point, _)| {
+                recorder.record_pixel(*point);
+                corners = Some(match corners {
+                    Some((min, max)) => (min.component_min(*point), max.component_max(*point)),
+                    None => (*point, *point),
+                });
+            }));
+
+        if let Some((min, max)) = corners {
+            recorder
+                .report
+                .bounding_boxes
+                .push(Rectangle::with_corners(min, max));
+        }
+        result
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.recorder.report.fill_contiguous_calls += 1;
+        self.recorder.record_area(area);
+        self.target.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.recorder.report.fill_solid_calls += 1;
+        self.recorder.record_area(area);
+        self.target.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.recorder.report.clear_calls += 1;
+        let area = self.bounding_box();
+        self.recorder.record_area(&area);
+        self.target.clear(color)
+    }
+}
+
+impl<D> Dimensions for DrawProfiler<D>
+where
+    D: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    use embedded_graphics::primitives::{Line, PrimitiveStyle};
+
+    #[test]
+    fn count_overdraw() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        display.set_allow_out_of_bounds_drawing(true);
+        let mut profiler = DrawProfiler::new(display);
+
+        let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+        Line::new(Point::new(0, 0), Point::new(3, 0))
+            .into_styled(style)
+            .draw(&mut profiler)
+            .unwrap();
+        Line::new(Point::new(2, 0), Point::new(2, 2))
+            .into_styled(style)
+            .draw(&mut profiler)
+            .unwrap();
+        profiler
+            .fill_solid(
+                &Rectangle::new(Point::new(-1, 0), Size::new(2, 1)),
+                BinaryColor::Off,
+            )
+            .unwrap();
+
+        let report = profiler.take_report();
+        assert_eq!(report.pixels, 4 + 3 + 2);
+        assert_eq!(report.unique_pixels, 6);
+        assert_eq!(report.overdrawn_pixels, 2);
+        assert_eq!(report.max_overdraw, 2);
+        assert_eq!(report.outside_pixels, 1);
+        assert_eq!(report.draw_iter_calls, 2);
+        assert_eq!(report.fill_solid_calls, 1);
+        assert_eq!(
+            report.bounding_box(),
+            Some(Rectangle::new(Point::new(-1, 0), Size::new(5, 3)))
+        );
+
+        assert_eq!(profiler.take_report(), DrawReport::default());
+    }
+}
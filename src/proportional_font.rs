@@ -0,0 +1,251 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Proportional text rendering
+//!
+//! `MonoFont` uses the same width for every glyph, which results in large gaps around narrow
+//! glyphs like `1` and `:`. A `ProportionalFont` has a width per glyph and a kerning table for
+//! adjusting the space between specific pairs of characters.
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Rectangle;
+
+/// Location and metrics of a single glyph in a `ProportionalFont`
+#[derive(Copy, Clone, Debug)]
+pub struct ProportionalGlyph {
+    /// Character represented by this glyph
+    pub character: char,
+    /// Horizontal offset of the glyph in the image of the font
+    pub offset: u32,
+    /// Width of the glyph in the image of the font
+    pub width: u32,
+    /// Horizontal distance from the start of this glyph to the start of the next glyph
+    pub advance: u32,
+}
+
+/// Bitmap font with a different width per glyph
+///
+/// The glyphs are stored next to each other in a single row with one bit per pixel. Each row of
+/// the image is padded to a whole byte and the leftmost pixel is stored in the most significant
+/// bit.
+pub struct ProportionalFont<'a> {
+    /// Pixels of the glyphs
+    pub data: &'a [u8],
+    /// Width of the image in pixels
+    pub image_width: u32,
+    /// Height of the glyphs
+    pub height: u32,
+    /// Offset from the top of a glyph to the baseline
+    pub baseline: u32,
+    /// Available glyphs
+    pub glyphs: &'a [ProportionalGlyph],
+    /// Glyph used for characters that are not available
+    pub replacement: ProportionalGlyph,
+    /// Adjustment of the advance between two characters, as `(left, right, adjustment)`
+    pub kerning: &'a [(char, char, i8)],
+}
+
+impl<'a> ProportionalFont<'a> {
+    fn glyph(&self, c: char) -> ProportionalGlyph {
+        self.glyphs
+            .iter()
+            .find(|glyph| glyph.character == c)
+            .copied()
+            .unwrap_or(self.replacement)
+    }
+
+    fn kerning(&self, left: char, right: char) -> i32 {
+        self.kerning
+            .iter()
+            .find(|(l, r, _)| *l == left && *r == right)
+            .map(|(_, _, adjustment)| *adjustment as i32)
+            .unwrap_or(0)
+    }
+
+    fn is_set(&self, point: Point) -> bool {
+        let bytes_per_row = self.image_width.div_ceil(8);
+        let byte = self.data[(point.y as u32 * bytes_per_row + point.x as u32 / 8) as usize];
+        byte & (0x80 >> (point.x % 8)) != 0
+    }
+
+    /// Iterate the glyphs of a text, together with their horizontal position
+    fn layout<'b>(&'b self, text: &'b str) -> impl Iterator<Item = (i32, ProportionalGlyph)> + 'b {
+        let mut x = 0;
+        let mut previous = None;
+        text.chars().map(move |c| {
+            if let Some(previous) = previous {
+                x += self.kerning(previous, c);
+            }
+            let glyph = self.glyph(c);
+            let position = x;
+            x += glyph.advance as i32;
+            previous = Some(c);
+            (position, glyph)
+        })
+    }
+
+    /// Width of a text, from the left of the first glyph to the right of the last glyph
+    fn text_width(&self, text: &str) -> u32 {
+        self.layout(text)
+            .map(|(x, glyph)| x + glyph.width as i32)
+            .max()
+            .unwrap_or(0) as u32
+    }
+}
+
+/// A single line of text in a proportional font
+///
+/// The position is the top left corner of the text, so that it can be aligned using
+/// `embedded_layout`.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use embedded_layout::prelude::*;
+/// use watchface::font::OVERPASS_DIGITS_PROPORTIONAL_64;
+/// use watchface::proportional_font::ProportionalText;
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_out_of_bounds_drawing(true);
+///
+/// ProportionalText::new("11:11", Point::zero(), &OVERPASS_DIGITS_PROPORTIONAL_64, Rgb888::WHITE)
+///     .align_to(&display.bounding_box(), horizontal::Center, vertical::Center)
+///     .draw(&mut display)
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone)]
+pub struct ProportionalText<'a, C> {
+    text: &'a str,
+    position: Point,
+    font: &'a ProportionalFont<'a>,
+    color: C,
+}
+
+impl<'a, C> ProportionalText<'a, C> {
+    /// Create text in the given color
+    pub fn new(text: &'a str, position: Point, font: &'a ProportionalFont<'a>, color: C) -> Self {
+        Self {
+            text,
+            position,
+            font,
+            color,
+        }
+    }
+}
+
+impl<C> Drawable for ProportionalText<'_, C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let font = self.font;
+
+        for (x, glyph) in font.layout(self.text) {
+            let glyph_position = self.position + Point::new(x, 0);
+            let glyph_area = Rectangle::new(
+                Point::new(glyph.offset as i32, 0),
+                Size::new(glyph.width, font.height),
+            );
+
+            let pixels = glyph_area
+                .points()
+                .filter(|point| font.is_set(*point))
+                .map(|point| Pixel(glyph_position + point - glyph_area.top_left, self.color));
+
+            display.draw_iter(pixels)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> Transform for ProportionalText<'_, C>
+where
+    C: Copy,
+{
+    fn translate(&self, by: Point) -> Self {
+        Self {
+            position: self.position + by,
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.position += by;
+        self
+    }
+}
+
+impl<C> Dimensions for ProportionalText<'_, C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            self.position,
+            Size::new(self.font.text_width(self.text), self.font.height),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    const fn glyph(character: char, offset: u32, width: u32, advance: u32) -> ProportionalGlyph {
+        ProportionalGlyph {
+            character,
+            offset,
+            width,
+            advance,
+        }
+    }
+
+    /// Glyphs of 2 pixels high: '1' is 1 pixel wide, '0' is 3 pixels wide and '?' is 2 pixels wide
+    const TEST_FONT: ProportionalFont = ProportionalFont {
+        data: &[0b1111_1100, 0b1101_0100],
+        image_width: 6,
+        height: 2,
+        baseline: 2,
+        glyphs: &[glyph('1', 0, 1, 2), glyph('0', 1, 3, 4)],
+        replacement: glyph('?', 4, 2, 3),
+        kerning: &[('1', '1', 1), ('0', '1', -1)],
+    };
+
+    #[test]
+    fn draw_with_kerning() {
+        let text = ProportionalText::new("1101?", Point::new(1, 1), &TEST_FONT, BinaryColor::On);
+
+        let mut display = MockDisplay::new();
+        text.draw(&mut display).unwrap();
+
+        display.assert_pattern(&["              ", " #  # #### ## ", " #  # # ##  # "]);
+        assert_eq!(
+            text.bounding_box(),
+            Rectangle::new(Point::new(1, 1), Size::new(12, 2))
+        );
+    }
+}
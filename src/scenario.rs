@@ -0,0 +1,348 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Scripted watchface data for simulations
+//!
+//! A scenario is a script of timestamped changes to the watchface data. It is played back from a
+//! fixed start time, optionally faster than real time, so that for example a whole day can be
+//! watched in a minute.
+//!
+//! # Script format
+//!
+//! Each line contains an offset from the start of the scenario (`H:MM:SS` or `H:MM`), the data to
+//! change and its new value. Empty lines and lines starting with `#` are ignored. The optional
+//! `start` line sets the time of day at which the scenario starts, otherwise it starts at
+//! midnight.
+//!
+//! ```text
+//! start 23:50
+//! 0:00 battery 20
+//! 0:00 charger discharging
+//! 0:03 battery 5
+//! 0:05 charger charging
+//! 0:10 notifications 2
+//! 0:30 time 07:00
+//! ```
+//!
+//! The supported data are `time` (`HH:MM[:SS]`), `battery` (percentage), `charger`
+//! (`discharging`, `charging`, `full`, `paused`, `over_temperature`, `fault` or `unknown`) and
+//! `notifications` (number of unread notifications, 0..255).
+
+use crate::battery::{ChargerState, StateOfCharge};
+use crate::time::Time;
+use crate::watchface_data::Watchface;
+use core::fmt;
+use core::time::Duration;
+use std::time::Instant;
+use std::vec::Vec;
+
+/// A single change of the watchface data
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Change {
+    /// Set the clock to a different time, from there it keeps running
+    Time(Time),
+    Battery(StateOfCharge),
+    Charger(ChargerState),
+    /// Set the number of unread notifications
+    Notifications(u8),
+}
+
+/// A change at a certain offset from the start of the scenario
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Event {
+    pub offset: Duration,
+    pub change: Change,
+}
+
+/// Error returned when parsing a scenario script fails
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ParseScenarioError {
+    /// Line number, starting at 1
+    pub line: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseScenarioError {}
+
+fn parse_offset(text: &str) -> Option<Duration> {
+    let mut parts = text.split(':').map(|part| part.parse::<u64>());
+    let hours = parts.next()?.ok()?;
+    let minutes = parts.next()?.ok()?;
+    let seconds = parts.next().unwrap_or(Ok(0)).ok()?;
+    if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    Some(Duration::from_secs((hours * 60 + minutes) * 60 + seconds))
+}
+
+fn parse_change(name: &str, value: &str) -> Result<Change, &'static str> {
+    match name {
+        "time" => value.parse().map(Change::Time).map_err(|_| "invalid time"),
+        "battery" => match value.parse() {
+            Ok(percentage) if percentage <= 100 => {
+                Ok(Change::Battery(StateOfCharge::from_percentage(percentage)))
+            }
+            _ => Err("invalid battery percentage"),
+        },
+        "charger" => value
+            .parse()
+            .map(Change::Charger)
+            .map_err(|_| "invalid charger state"),
+        "notifications" => value
+            .parse()
+            .map(Change::Notifications)
+            .map_err(|_| "invalid notification count"),
+        _ => Err("unknown data"),
+    }
+}
+
+/// A script of changes to the watchface data
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use watchface::battery::StateOfCharge;
+/// use watchface::scenario::Scenario;
+/// use watchface::time::Time;
+///
+/// let scenario = Scenario::parse("start 23:50\n0:03 battery 5\n").unwrap();
+///
+/// let watchface = scenario.watchface_at(Duration::from_secs(4 * 60));
+/// assert_eq!(watchface.time, Some(Time::from_values(23, 54, 0)));
+/// assert_eq!(watchface.battery, Some(StateOfCharge::from_percentage(5)));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Scenario {
+    start: Option<Time>,
+    events: Vec<Event>,
+}
+
+impl Scenario {
+    /// Parse a scenario script
+    pub fn parse(script: &str) -> Result<Self, ParseScenarioError> {
+        let mut scenario = Scenario::default();
+
+        for (index, line) in script.lines().enumerate() {
+            let error = |message| ParseScenarioError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                ["start", time] => {
+                    let time = time.parse().map_err(|_| error("invalid start time"))?;
+                    scenario.start = Some(time);
+                }
+                [offset, name, value] => {
+                    let offset = parse_offset(offset).ok_or_else(|| error("invalid offset"))?;
+                    let change = parse_change(name, value).map_err(error)?;
+                    scenario.events.push(Event { offset, change });
+                }
+                _ => return Err(error("expected: <offset> <data> <value>")),
+            }
+        }
+
+        scenario.events.sort_by_key(|event| event.offset);
+        Ok(scenario)
+    }
+
+    /// Returns the time of day at which the scenario starts, if it is set
+    pub fn start(&self) -> Option<Time> {
+        self.start
+    }
+
+    /// Change the time of day at which the scenario starts
+    pub fn with_start(mut self, start: Time) -> Self {
+        self.start = Some(start);
+
+        self
+    }
+
+    /// Returns the changes, ordered by offset
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns the watchface data at an offset from the start of the scenario
+    pub fn watchface_at(&self, offset: Duration) -> Watchface {
+        let mut clock = (Duration::from_secs(0), self.start.unwrap_or_default());
+        let mut watchface = Watchface::default();
+
+        for event in self
+            .events
+            .iter()
+            .take_while(|event| event.offset <= offset)
+        {
+            match event.change {
+                Change::Time(time) => clock = (event.offset, time),
+                Change::Battery(battery) => watchface.battery = Some(battery),
+                Change::Charger(charger) => watchface.charger = Some(charger),
+                Change::Notifications(count) => watchface.notifications = Some(count),
+            }
+        }
+
+        let (clock_offset, clock_time) = clock;
+        watchface.time = Some(clock_time + (offset - clock_offset));
+        watchface
+    }
+}
+
+/// Plays a scenario in real time, optionally sped up
+pub struct ScenarioPlayer {
+    scenario: Scenario,
+    speed: f32,
+    started: Instant,
+}
+
+impl ScenarioPlayer {
+    /// Start playing a scenario, a speed of 60 plays one minute per second
+    ///
+    /// # Panics
+    ///
+    /// Panics when the speed is not a finite, positive number.
+    pub fn new(scenario: Scenario, speed: f32) -> Self {
+        assert!(speed.is_finite() && speed > 0.0, "invalid scenario speed");
+
+        Self {
+            scenario,
+            speed,
+            started: Instant::now(),
+        }
+    }
+
+    /// Returns the offset from the start of the scenario, saturating at the longest duration
+    pub fn offset(&self) -> Duration {
+        Duration::try_from_secs_f32(self.started.elapsed().as_secs_f32() * self.speed)
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the current watchface data
+    pub fn watchface(&self) -> Watchface {
+        self.scenario.watchface_at(self.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "
+        # Comments and empty lines are ignored
+        start 23:50
+
+        0:00 battery 20
+        0:00 charger discharging
+        0:03:30 battery 5
+        0:05 charger charging
+        0:10 notifications 2
+        1:00 time 07:00
+    ";
+
+    #[test]
+    fn parse_and_play() {
+        let scenario = Scenario::parse(SCRIPT).unwrap();
+        assert_eq!(scenario.start(), Some(Time::from_values(23, 50, 0)));
+        assert_eq!(scenario.events().len(), 6);
+
+        let watchface = scenario.watchface_at(Duration::from_secs(3 * 60));
+        assert_eq!(watchface.time, Some(Time::from_values(23, 53, 0)));
+        assert_eq!(watchface.battery, Some(StateOfCharge::from_percentage(20)));
+        assert_eq!(watchface.charger, Some(ChargerState::Discharging));
+        assert_eq!(watchface.notifications, None);
+
+        let watchface = scenario.watchface_at(Duration::from_secs(11 * 60));
+        assert_eq!(watchface.time, Some(Time::from_values(0, 1, 0)));
+        assert_eq!(watchface.battery, Some(StateOfCharge::from_percentage(5)));
+        assert_eq!(watchface.charger, Some(ChargerState::Charging));
+        assert_eq!(watchface.notifications, Some(2));
+
+        let watchface = scenario.watchface_at(Duration::from_secs(62 * 60));
+        assert_eq!(watchface.time, Some(Time::from_values(7, 2, 0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Scenario::parse("0:00 battery 20\n0:01 battery 101"),
+            Err(ParseScenarioError {
+                line: 2,
+                message: "invalid battery percentage"
+            })
+        );
+        assert_eq!(
+            Scenario::parse("0:60 charger full"),
+            Err(ParseScenarioError {
+                line: 1,
+                message: "invalid offset"
+            })
+        );
+        assert_eq!(
+            Scenario::parse("0:00 notifications hello"),
+            Err(ParseScenarioError {
+                line: 1,
+                message: "invalid notification count"
+            })
+        );
+        assert_eq!(
+            Scenario::parse("0:00 weather sunny"),
+            Err(ParseScenarioError {
+                line: 1,
+                message: "unknown data"
+            })
+        );
+    }
+
+    fn play(speed: f32) -> ScenarioPlayer {
+        ScenarioPlayer::new(Scenario::parse(SCRIPT).unwrap(), speed)
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid scenario speed")]
+    fn speed_zero() {
+        play(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid scenario speed")]
+    fn speed_negative() {
+        play(-1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid scenario speed")]
+    fn speed_nan() {
+        play(f32::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid scenario speed")]
+    fn speed_infinite() {
+        play(f32::INFINITY);
+    }
+}
@@ -0,0 +1,370 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Seven-segment digits
+//!
+//! The digits are drawn using lines, so they can be drawn at any size without storing a font.
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle, Rectangle};
+
+/// Segments for the hexadecimal digits, bit 0 is segment A and bit 6 is segment G
+const DIGIT_SEGMENTS: [u8; 16] = [
+    0b011_1111, // 0
+    0b000_0110, // 1
+    0b101_1011, // 2
+    0b100_1111, // 3
+    0b110_0110, // 4
+    0b110_1101, // 5
+    0b111_1101, // 6
+    0b000_0111, // 7
+    0b111_1111, // 8
+    0b110_1111, // 9
+    0b111_0111, // A
+    0b111_1100, // b
+    0b011_1001, // C
+    0b101_1110, // d
+    0b111_1001, // E
+    0b111_0001, // F
+];
+
+/// Segments for a minus sign
+pub const MINUS_SEGMENTS: u8 = 0b100_0000;
+
+/// A single seven-segment digit
+///
+/// The segments are named A to G, clockwise starting at the top with G in the middle.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use watchface::seven_segment::SevenSegmentDigitBuilder;
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_overdraw(true);
+///
+/// SevenSegmentDigitBuilder::new(Point::zero())
+///     .with_digit(7)
+///     .with_size(Size::new(20, 40))
+///     .with_thickness(4)
+///     .with_slant(4)
+///     .with_off_color(Rgb888::new(40, 40, 40))
+///     .build()
+///     .draw(&mut display)
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone)]
+pub struct SevenSegmentDigit<C> {
+    position: Point,
+    segments: u8,
+    size: Size,
+    thickness: u32,
+    slant: i32,
+    on_color: C,
+    off_color: Option<C>,
+}
+
+impl<C> SevenSegmentDigit<C> {
+    /// Start and end of the center line of each segment, before slanting
+    fn segment_lines(&self) -> [(Point, Point); 7] {
+        let half = (self.thickness / 2) as i32;
+        let gap = half + 1;
+        let left = half;
+        let right = self.size.width as i32 - 1 - half;
+        let top = half;
+        let middle = (self.size.height / 2) as i32;
+        let bottom = self.size.height as i32 - 1 - half;
+
+        [
+            (Point::new(left + gap, top), Point::new(right - gap, top)),
+            (
+                Point::new(right, top + gap),
+                Point::new(right, middle - gap),
+            ),
+            (
+                Point::new(right, middle + gap),
+                Point::new(right, bottom - gap),
+            ),
+            (
+                Point::new(left + gap, bottom),
+                Point::new(right - gap, bottom),
+            ),
+            (
+                Point::new(left, middle + gap),
+                Point::new(left, bottom - gap),
+            ),
+            (Point::new(left, top + gap), Point::new(left, middle - gap)),
+            (
+                Point::new(left + gap, middle),
+                Point::new(right - gap, middle),
+            ),
+        ]
+    }
+
+    /// Move a point to the right, proportional to its distance from the bottom
+    fn slanted(&self, point: Point) -> Point {
+        let height = self.size.height as i32 - 1;
+        let offset = if height > 0 {
+            self.slant * (height - point.y) / height
+        } else {
+            0
+        };
+        self.position + point + Point::new(offset + (-self.slant).max(0), 0)
+    }
+}
+
+impl<C> Drawable for SevenSegmentDigit<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D>(&self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        for (index, (start, end)) in self.segment_lines().iter().enumerate() {
+            let color = if self.segments & (1 << index) != 0 {
+                self.on_color
+            } else if let Some(off_color) = self.off_color {
+                off_color
+            } else {
+                continue;
+            };
+
+            Line::new(self.slanted(*start), self.slanted(*end))
+                .into_styled(PrimitiveStyle::with_stroke(color, self.thickness))
+                .draw(display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C> Transform for SevenSegmentDigit<C>
+where
+    C: Copy,
+{
+    fn translate(&self, by: Point) -> Self {
+        Self {
+            position: self.position + by,
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.position += by;
+        self
+    }
+}
+
+impl<C> Dimensions for SevenSegmentDigit<C> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(
+            self.position,
+            self.size + Size::new(self.slant.unsigned_abs(), 0),
+        )
+    }
+}
+
+pub struct SevenSegmentDigitBuilder<C> {
+    digit: SevenSegmentDigit<C>,
+}
+
+impl<C> SevenSegmentDigitBuilder<C>
+where
+    C: RgbColor,
+{
+    pub fn new(position: Point) -> Self {
+        Self {
+            digit: SevenSegmentDigit {
+                position,
+                segments: 0,
+                size: Size::new(20, 40),
+                thickness: 4,
+                slant: 0,
+                on_color: C::WHITE,
+                off_color: None,
+            },
+        }
+    }
+}
+
+impl<C> SevenSegmentDigitBuilder<C> {
+    /// Light the segments of a hexadecimal digit (0..15)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digit is larger than 15.
+    pub fn with_digit(mut self, digit: u8) -> Self {
+        assert!(digit < 16);
+        self.digit.segments = DIGIT_SEGMENTS[digit as usize];
+
+        self
+    }
+
+    /// Light the given segments, bit 0 is segment A and bit 6 is segment G
+    pub fn with_segments(mut self, segments: u8) -> Self {
+        self.digit.segments = segments;
+
+        self
+    }
+
+    /// Size of the digit without slant (default 20x40)
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.digit.size = size;
+
+        self
+    }
+
+    /// Thickness of the segments (default 4)
+    pub fn with_thickness(mut self, thickness: u32) -> Self {
+        self.digit.thickness = thickness;
+
+        self
+    }
+
+    /// Horizontal offset of the top relative to the bottom, positive leans to the right
+    pub fn with_slant(mut self, slant: i32) -> Self {
+        self.digit.slant = slant;
+
+        self
+    }
+
+    /// Color of the lit segments (default white)
+    pub fn with_on_color(mut self, color: C) -> Self {
+        self.digit.on_color = color;
+
+        self
+    }
+
+    /// Draw the unlit segments as a dim ghost in the given color
+    pub fn with_off_color(mut self, color: C) -> Self {
+        self.digit.off_color = Some(color);
+
+        self
+    }
+
+    pub fn build(self) -> SevenSegmentDigit<C> {
+        self.digit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::mock_display::MockDisplay;
+    use embedded_graphics::pixelcolor::Rgb888;
+
+    #[test]
+    fn digit_four_with_ghost() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        SevenSegmentDigitBuilder::new(Point::new(0, 0))
+            .with_digit(4)
+            .with_size(Size::new(6, 9))
+            .with_thickness(1)
+            .with_off_color(Rgb888::BLUE)
+            .build()
+            .draw(&mut display)
+            .unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            " BBBB ",
+            "W    W",
+            "W    W",
+            "W    W",
+            " WWWW ",
+            "B    W",
+            "B    W",
+            "B    W",
+            " BBBB ",
+        ]);
+    }
+
+    #[test]
+    fn slanted_digit_one() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        let digit = SevenSegmentDigitBuilder::new(Point::new(0, 0))
+            .with_digit(1)
+            .with_size(Size::new(6, 9))
+            .with_thickness(1)
+            .with_slant(4)
+            .build();
+        digit.draw(&mut display).unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "          ",
+            "        W ",
+            "        W ",
+            "       W  ",
+            "          ",
+            "      W   ",
+            "      W   ",
+            "     W    ",
+            "          ",
+        ]);
+        assert_eq!(
+            digit.bounding_box(),
+            Rectangle::new(Point::zero(), Size::new(10, 9))
+        );
+    }
+
+    #[test]
+    fn negative_slant_leans_left() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        let digit = SevenSegmentDigitBuilder::new(Point::new(0, 0))
+            .with_digit(1)
+            .with_size(Size::new(6, 9))
+            .with_thickness(1)
+            .with_slant(-4)
+            .build();
+        digit.draw(&mut display).unwrap();
+
+        #[rustfmt::skip]
+        display.assert_pattern(&[
+            "          ",
+            "      W   ",
+            "      W   ",
+            "       W  ",
+            "          ",
+            "        W ",
+            "        W ",
+            "         W",
+            "          ",
+        ]);
+        assert_eq!(
+            digit.bounding_box(),
+            Rectangle::new(Point::zero(), Size::new(10, 9))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "digit < 16")]
+    fn digit_out_of_range() {
+        SevenSegmentDigitBuilder::<Rgb888>::new(Point::zero()).with_digit(16);
+    }
+}
@@ -15,14 +15,17 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::anti_aliased_font::AntiAliasedText;
+use crate::background::{Background, DrawOnBackground, PlainBackground};
 use crate::battery_icon::{BatteryIconBuilder, ChargerAlignment};
-use crate::font::OVERPASS_NUMBERS_FONT;
+use crate::font::{OVERPASS_DIGITS_AA_64, OVERPASS_NUMBERS_FONT};
 use crate::styled::Styled;
 use crate::watchface_data::Watchface;
 use core::fmt::Write;
 use core::marker::PhantomData;
 use embedded_graphics::{
-    draw_target::DrawTarget, mono_font::MonoTextStyle, prelude::*, text::Text, Drawable,
+    draw_target::DrawTarget, mono_font::MonoTextStyle, pixelcolor::Rgb888, prelude::*, text::Text,
+    Drawable,
 };
 use embedded_layout::prelude::*;
 use heapless::consts::*;
@@ -60,12 +63,31 @@ use heapless::String;
 /// ```
 #[derive(Default)]
 pub struct SimpleWatchfaceStyle<C> {
+    anti_aliasing: bool,
     _phantom_data: PhantomData<C>,
 }
 
+impl<C> SimpleWatchfaceStyle<C> {
+    /// Draw the time with smooth edges, instead of the default bitmap font
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::pixelcolor::Rgb565;
+    /// use watchface::SimpleWatchfaceStyle;
+    ///
+    /// let style = SimpleWatchfaceStyle::<Rgb565>::default().with_anti_aliasing(true);
+    /// ```
+    pub fn with_anti_aliasing(mut self, anti_aliasing: bool) -> Self {
+        self.anti_aliasing = anti_aliasing;
+
+        self
+    }
+}
+
 impl<C> Drawable for Styled<Watchface, SimpleWatchfaceStyle<C>>
 where
-    C: RgbColor,
+    C: RgbColor + From<Rgb888>,
 {
     type Color = C;
 
@@ -75,9 +97,22 @@ where
         &self,
         display: &mut D,
     ) -> Result<(), <D as DrawTarget>::Error> {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, SimpleWatchfaceStyle<C>>
+where
+    C: RgbColor + From<Rgb888>,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
         let display_area = display.bounding_box();
 
-        display.clear(C::BLACK)?;
+        background.draw_background(&display_area, C::BLACK, display)?;
 
         if let Some(time) = &self.watchface.time {
             let time_text_style = MonoTextStyle::new(&OVERPASS_NUMBERS_FONT, C::WHITE);
@@ -91,9 +126,21 @@ where
             )
             .unwrap();
 
-            Text::new(&text, Point::new(10, 70), time_text_style)
+            if self.style.anti_aliasing {
+                AntiAliasedText::new(
+                    &text,
+                    Point::zero(),
+                    &OVERPASS_DIGITS_AA_64,
+                    C::WHITE,
+                    C::BLACK,
+                )
                 .align_to(&display_area, horizontal::Center, vertical::Center)
-                .draw(display)?;
+                .draw_on_background(display, background)?;
+            } else {
+                Text::new(&text, Point::new(10, 70), time_text_style)
+                    .align_to(&display_area, horizontal::Center, vertical::Center)
+                    .draw(display)?;
+            }
         }
 
         let mut icon_builder = BatteryIconBuilder::new(Point::new(10, 10));
@@ -103,6 +150,9 @@ where
         if let Some(charger) = &self.watchface.charger {
             icon_builder = icon_builder.with_charger(*charger);
         }
+        if let Some(animation_time) = self.watchface.animation_time {
+            icon_builder = icon_builder.with_animation_time(animation_time);
+        }
         icon_builder
             .with_charger_alignment(ChargerAlignment::Left)
             .build()
@@ -0,0 +1,208 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Terminal draw target
+//!
+//! Shows a watchface in a terminal with 24-bit color support, for example over SSH. Each
+//! character cell shows two pixels on top of each other, using the upper half block character
+//! with a foreground and background color.
+
+use crate::framebuffer::Framebuffer;
+use core::fmt::Write as _;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use std::io;
+use std::string::String;
+
+/// A draw target that can be written to a terminal using ANSI escape codes
+///
+/// The display can be scaled down, so that it fits in the terminal. Each block of
+/// `scale` by `scale` pixels is averaged into a single terminal pixel.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use embedded_graphics::prelude::*;
+/// use watchface::terminal::TerminalDisplay;
+/// use watchface::time::Time;
+/// use watchface::{SimpleWatchfaceStyle, Watchface};
+///
+/// let mut display = TerminalDisplay::<Rgb888>::new(Size::new(240, 240)).with_scale(2);
+///
+/// Watchface::build()
+///     .with_time(Time::from_values(10, 9, 0))
+///     .into_styled(SimpleWatchfaceStyle::default())
+///     .draw(&mut display)
+///     .unwrap();
+///
+/// let mut output = Vec::new();
+/// display.write_to(&mut output).unwrap();
+/// ```
+pub struct TerminalDisplay<C> {
+    framebuffer: Framebuffer<C>,
+    scale: u32,
+}
+
+impl<C> TerminalDisplay<C>
+where
+    C: PixelColor + Into<Rgb888> + From<Rgb888>,
+{
+    /// Create a black display
+    pub fn new(size: Size) -> Self {
+        Self {
+            framebuffer: Framebuffer::new(size, Rgb888::BLACK.into()),
+            scale: 1,
+        }
+    }
+
+    /// Scale the display down by an integer factor (default 1)
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        assert!(scale > 0);
+        self.scale = scale;
+
+        self
+    }
+
+    /// Average color of a scaled pixel, pixels outside of the display are black
+    fn scaled_pixel(&self, x: u32, y: u32) -> Rgb888 {
+        let mut sum = [0u32; 3];
+        for dy in 0..self.scale {
+            for dx in 0..self.scale {
+                let point = Point::new((x * self.scale + dx) as i32, (y * self.scale + dy) as i32);
+                if let Some(color) = self.framebuffer.pixel(point) {
+                    let color: Rgb888 = color.into();
+                    sum[0] += color.r() as u32;
+                    sum[1] += color.g() as u32;
+                    sum[2] += color.b() as u32;
+                }
+            }
+        }
+        let count = self.scale * self.scale;
+        Rgb888::new(
+            (sum[0] / count) as u8,
+            (sum[1] / count) as u8,
+            (sum[2] / count) as u8,
+        )
+    }
+
+    /// Returns the display as lines of text with ANSI escape codes
+    pub fn render(&self) -> String {
+        let size = self.framebuffer.size();
+        let columns = size.width.div_ceil(self.scale);
+        let rows = size.height.div_ceil(self.scale);
+
+        let mut text = String::new();
+        for y in (0..rows).step_by(2) {
+            // Only change the colors when needed, to limit the amount of output
+            let mut previous = None;
+            for x in 0..columns {
+                let colors = (self.scaled_pixel(x, y), self.scaled_pixel(x, y + 1));
+                if previous != Some(colors) {
+                    let (top, bottom) = colors;
+                    write!(
+                        text,
+                        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m",
+                        top.r(),
+                        top.g(),
+                        top.b(),
+                        bottom.r(),
+                        bottom.g(),
+                        bottom.b()
+                    )
+                    .unwrap();
+                    previous = Some(colors);
+                }
+                text.push('\u{2580}');
+            }
+            text.push_str("\x1b[0m\n");
+        }
+        text
+    }
+
+    /// Write the display to a terminal
+    pub fn write_to<W: io::Write>(&self, output: &mut W) -> io::Result<()> {
+        output.write_all(self.render().as_bytes())
+    }
+}
+
+impl<C> DrawTarget for TerminalDisplay<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.framebuffer.draw_iter(pixels)
+    }
+}
+
+impl<C> OriginDimensions for TerminalDisplay<C> {
+    fn size(&self) -> Size {
+        self.framebuffer.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_half_blocks() {
+        let mut display = TerminalDisplay::<Rgb888>::new(Size::new(2, 2));
+        display
+            .draw_iter([
+                Pixel(Point::new(0, 0), Rgb888::new(1, 2, 3)),
+                Pixel(Point::new(1, 1), Rgb888::WHITE),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            display.render(),
+            "\x1b[38;2;1;2;3m\x1b[48;2;0;0;0m\u{2580}\
+             \x1b[38;2;0;0;0m\x1b[48;2;255;255;255m\u{2580}\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn render_repeated_colors_once() {
+        let display = TerminalDisplay::<Rgb888>::new(Size::new(3, 2));
+
+        assert_eq!(
+            display.render(),
+            "\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m\u{2580}\u{2580}\u{2580}\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn render_scaled() {
+        let mut display = TerminalDisplay::<Rgb888>::new(Size::new(2, 2)).with_scale(2);
+        display
+            .draw_iter([Pixel(Point::new(0, 0), Rgb888::new(200, 100, 40))])
+            .unwrap();
+
+        assert_eq!(
+            display.render(),
+            "\x1b[38;2;50;25;10m\x1b[48;2;0;0;0m\u{2580}\x1b[0m\n"
+        );
+    }
+}
@@ -15,9 +15,11 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::background::{Background, DrawOnBackground, PlainBackground};
 use crate::battery::ChargerState;
+use crate::phrase::{time_to_words, Language};
 use crate::styled::Styled;
-use crate::time::Time;
+use crate::time::{Granularity, RoundingMode, Time};
 use crate::Watchface;
 use core::fmt::Write;
 use core::marker::PhantomData;
@@ -36,56 +38,18 @@ use embedded_layout::prelude::*;
 use heapless::consts::*;
 use heapless::String;
 
-fn convert_hours_to_text(hours: u8) -> &'static str {
-    match hours % 12 {
-        0 => "twaalf",
-        1 => "één",
-        2 => "twee",
-        3 => "drie",
-        4 => "vier",
-        5 => "vijf",
-        6 => "zes",
-        7 => "zeven",
-        8 => "acht",
-        9 => "negen",
-        10 => "tien",
-        11 => "elf",
-        _ => "",
-    }
-}
-
 fn convert_time_to_text(time: &Time) -> String<U20> {
     let mut text = String::<U20>::new();
 
-    let rounded_time = time.round_to_quarters();
-    if rounded_time.minutes_local() == 0 {
-        write!(
-            &mut text,
-            "{}\nuur",
-            convert_hours_to_text(rounded_time.hours_local())
-        )
-        .unwrap()
-    } else if rounded_time.minutes_local() == 15 {
-        write!(
-            &mut text,
-            "kwart\nover\n{}",
-            convert_hours_to_text(rounded_time.hours_local())
-        )
-        .unwrap()
-    } else if rounded_time.minutes_local() == 30 {
-        write!(
-            &mut text,
-            "half\n{}",
-            convert_hours_to_text(rounded_time.hours_local() + 1)
-        )
-        .unwrap()
-    } else if rounded_time.minutes_local() == 45 {
-        write!(
-            &mut text,
-            "kwart\nvoor\n{}",
-            convert_hours_to_text(rounded_time.hours_local() + 1)
-        )
-        .unwrap()
+    let rounded_time = time.round_to(Granularity::QuarterHour, RoundingMode::Nearest);
+    for (index, word) in time_to_words(&rounded_time, Language::Dutch)
+        .iter()
+        .enumerate()
+    {
+        if index > 0 {
+            text.push('\n').unwrap();
+        }
+        text.push_str(word.text(Language::Dutch)).unwrap();
     }
 
     text
@@ -108,15 +72,27 @@ where
         &self,
         display: &mut D,
     ) -> Result<(), <D as DrawTarget>::Error> {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, TextualTimeWatchfaceStyle<C>>
+where
+    C: RgbColor,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
         let display_area = display.bounding_box();
 
-        display.clear(C::BLACK)?;
+        background.draw_background(&display_area, C::BLACK, display)?;
 
         if let Some(time) = &self.watchface.time {
             let time_text_style = MonoTextStyleBuilder::new()
                 .font(&FONT_10X20)
                 .text_color(C::WHITE)
-                .background_color(C::BLACK)
                 .build();
 
             let text = convert_time_to_text(time);
@@ -137,6 +113,10 @@ where
         let charger_text = match &self.watchface.charger {
             Some(ChargerState::Charging) => "Charging",
             Some(ChargerState::Full) => "Full",
+            Some(ChargerState::Paused) => "Paused",
+            Some(ChargerState::OverTemperature) => "Too hot",
+            Some(ChargerState::Fault) => "Fault",
+            Some(ChargerState::Unknown) => "Unknown",
             Some(ChargerState::Discharging) => "",
             None => "",
         };
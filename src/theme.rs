@@ -0,0 +1,57 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Color themes for watchface styles
+
+use embedded_graphics::pixelcolor::RgbColor;
+
+/// Colors used by a watchface style
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::Rgb565;
+/// use embedded_graphics::prelude::*;
+/// use watchface::theme::Theme;
+///
+/// let theme = Theme {
+///     accent: Rgb565::YELLOW,
+///     ..Theme::default()
+/// };
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Theme<C> {
+    /// Color of the empty screen
+    pub background: C,
+    /// Color of the main elements
+    pub foreground: C,
+    /// Color of the elements that need to stand out
+    pub accent: C,
+}
+
+impl<C> Default for Theme<C>
+where
+    C: RgbColor,
+{
+    fn default() -> Self {
+        Self {
+            background: C::BLACK,
+            foreground: C::WHITE,
+            accent: C::CYAN,
+        }
+    }
+}
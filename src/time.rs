@@ -15,11 +15,31 @@
  * along with this program.  If not, see <http://www.gnu.org/licenses/>.
  */
 
+use core::fmt;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+
 /// Simple representation of time
 ///
 /// This is a simplified representation of time, so that it can also be used in applications without
 /// a full operating system or without chrono dependency.
-#[derive(Default, Eq, PartialEq, Debug, Clone, Copy)]
+///
+/// Times are ordered from midnight to the end of the day. A `core::time::Duration` can be added
+/// or subtracted, wrapping around midnight.
+///
+/// # Examples
+/// ```
+/// use core::time::Duration;
+/// use watchface::time::Time;
+///
+/// let alarm = Time::from_values(7, 30, 0);
+/// let now = Time::from_values(23, 0, 0);
+/// assert!(now > alarm);
+/// assert_eq!(alarm - now, Duration::from_secs(8 * 60 * 60 + 30 * 60));
+/// assert_eq!(now + Duration::from_secs(90 * 60), Time::from_values(0, 30, 0));
+/// ```
+#[derive(Default, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
 pub struct Time {
     hours_local: u8,
     minutes_local: u8,
@@ -30,9 +50,12 @@ impl Time {
     /// Returns a time from the individual values
     ///
     /// # Arguments
-    /// * `hours_local` - Hours
-    /// * `minutes_local` - Minutes
-    /// * `seconds_local` - Seconds
+    /// * `hours_local` - Hours (0..24)
+    /// * `minutes_local` - Minutes (0..60)
+    /// * `seconds_local` - Seconds (0..60)
+    ///
+    /// # Panics
+    /// Panics if one of the values is out of range, see `try_from_values` for a checked version.
     ///
     /// # Examples
     /// ```
@@ -42,11 +65,35 @@ impl Time {
     /// assert_eq!(time.minutes_local(), 15);
     /// assert_eq!(time.seconds_local(), 25);
     /// ```
-    pub fn from_values(hours_local: u8, minutes_local: u8, seconds_local: u8) -> Self {
-        Time {
-            hours_local,
-            minutes_local,
-            seconds_local,
+    pub const fn from_values(hours_local: u8, minutes_local: u8, seconds_local: u8) -> Self {
+        match Self::try_from_values(hours_local, minutes_local, seconds_local) {
+            Some(time) => time,
+            None => panic!("time value out of range"),
+        }
+    }
+
+    /// Returns a time from the individual values, or `None` if a value is out of range
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::time::Time;
+    /// assert_eq!(Time::try_from_values(23, 59, 59), Some(Time::from_values(23, 59, 59)));
+    /// assert_eq!(Time::try_from_values(24, 0, 0), None);
+    /// assert_eq!(Time::try_from_values(12, 60, 0), None);
+    /// ```
+    pub const fn try_from_values(
+        hours_local: u8,
+        minutes_local: u8,
+        seconds_local: u8,
+    ) -> Option<Self> {
+        if hours_local < 24 && minutes_local < 60 && seconds_local < 60 {
+            Some(Time {
+                hours_local,
+                minutes_local,
+                seconds_local,
+            })
+        } else {
+            None
         }
     }
 
@@ -77,22 +124,90 @@ impl Time {
     }
 
     /// Get hours in local timezone
-    pub fn hours_local(&self) -> u8 {
+    pub const fn hours_local(&self) -> u8 {
         self.hours_local
     }
 
     /// Get minutes in local timezone
-    pub fn minutes_local(&self) -> u8 {
+    pub const fn minutes_local(&self) -> u8 {
         self.minutes_local
     }
 
     /// Get seconds in local timezone
-    pub fn seconds_local(&self) -> u8 {
+    pub const fn seconds_local(&self) -> u8 {
         self.seconds_local
     }
 
+    /// Returns the number of seconds since midnight
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::time::Time;
+    /// assert_eq!(Time::from_values(1, 2, 3).seconds_since_midnight(), 3723);
+    /// ```
+    pub const fn seconds_since_midnight(&self) -> u32 {
+        (self.hours_local as u32 * 60 + self.minutes_local as u32) * 60 + self.seconds_local as u32
+    }
+
+    /// Returns a time from the number of seconds since midnight, wrapping at 24 hours
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::time::Time;
+    /// assert_eq!(Time::from_seconds_since_midnight(3723), Time::from_values(1, 2, 3));
+    /// assert_eq!(Time::from_seconds_since_midnight(86400 + 60), Time::from_values(0, 1, 0));
+    /// ```
+    pub const fn from_seconds_since_midnight(seconds: u32) -> Self {
+        let seconds = seconds % SECONDS_PER_DAY;
+        Time {
+            hours_local: (seconds / 60 / 60) as u8,
+            minutes_local: (seconds / 60 % 60) as u8,
+            seconds_local: (seconds % 60) as u8,
+        }
+    }
+
+    /// Round a time to a multiple of the granularity
+    ///
+    /// The seconds are taken into account, so that 10:02:30 rounds to 10:05 with
+    /// `RoundingMode::Nearest`. A time that rounds up to midnight wraps to 00:00.
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::time::{Granularity, RoundingMode, Time};
+    ///
+    /// let time = Time::from_values(10, 22, 40);
+    /// assert_eq!(
+    ///     time.round_to(Granularity::TenMinutes, RoundingMode::Floor),
+    ///     Time::from_values(10, 20, 0)
+    /// );
+    /// assert_eq!(
+    ///     time.round_to(Granularity::FiveMinutes, RoundingMode::Nearest),
+    ///     Time::from_values(10, 25, 0)
+    /// );
+    /// assert_eq!(
+    ///     time.round_to(Granularity::HalfHour, RoundingMode::Ceil),
+    ///     Time::from_values(10, 30, 0)
+    /// );
+    /// assert_eq!(
+    ///     Time::from_values(23, 53, 0).round_to(Granularity::QuarterHour, RoundingMode::Nearest),
+    ///     Time::from_values(0, 0, 0)
+    /// );
+    /// ```
+    pub fn round_to(self, granularity: Granularity, mode: RoundingMode) -> Time {
+        let step = granularity.seconds();
+        let seconds = self.seconds_since_midnight();
+        let rounded = match mode {
+            RoundingMode::Floor => seconds / step * step,
+            RoundingMode::Nearest => (seconds + step / 2) / step * step,
+            RoundingMode::Ceil => seconds.div_ceil(step) * step,
+        };
+        Self::from_seconds_since_midnight(rounded)
+    }
+
     /// Round a time to its closest quarter
     ///
+    /// This is the same as `round_to(Granularity::QuarterHour, RoundingMode::Nearest)`.
+    ///
     /// # Examples
     /// ```
     /// use watchface::time::Time;
@@ -112,34 +227,222 @@ impl Time {
     /// assert_eq!(rounded.seconds_local(), 0);
     /// ```
     pub fn round_to_quarters(self) -> Time {
-        let mut hours = self.hours_local;
-        let mut minutes = self.minutes_local;
-        if minutes <= 7 {
-            minutes = 0;
-        } else if minutes > 7 && minutes <= 22 {
-            minutes = 15;
-        } else if minutes > 22 && minutes <= 37 {
-            minutes = 30;
-        } else if minutes > 37 && minutes <= 52 {
-            minutes = 45;
-        } else {
-            hours += 1;
-            minutes = 0;
+        self.round_to(Granularity::QuarterHour, RoundingMode::Nearest)
+    }
+
+    /// Round a time to its closest multiple of five minutes
+    ///
+    /// This is the same as `round_to(Granularity::FiveMinutes, RoundingMode::Nearest)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use watchface::time::Time;
+    ///
+    /// let rounded = Time::from_values(10, 22, 20).round_to_five_minutes();
+    /// assert_eq!(rounded, Time::from_values(10, 20, 0));
+    ///
+    /// let rounded = Time::from_values(10, 23, 0).round_to_five_minutes();
+    /// assert_eq!(rounded, Time::from_values(10, 25, 0));
+    ///
+    /// let rounded = Time::from_values(23, 58, 0).round_to_five_minutes();
+    /// assert_eq!(rounded, Time::from_values(0, 0, 0));
+    /// ```
+    pub fn round_to_five_minutes(self) -> Time {
+        self.round_to(Granularity::FiveMinutes, RoundingMode::Nearest)
+    }
+}
+
+/// Format a time as `HH:MM:SS`
+///
+/// # Examples
+/// ```
+/// use watchface::time::Time;
+/// assert_eq!(Time::from_values(7, 8, 9).to_string(), "07:08:09");
+/// ```
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            self.hours_local, self.minutes_local, self.seconds_local
+        )
+    }
+}
+
+/// Error returned when parsing a `Time` fails
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct ParseTimeError;
+
+/// Parse a time in the format `HH:MM` or `HH:MM:SS`
+///
+/// # Examples
+/// ```
+/// use watchface::time::Time;
+/// assert_eq!("10:09".parse(), Ok(Time::from_values(10, 9, 0)));
+/// assert_eq!("23:59:30".parse(), Ok(Time::from_values(23, 59, 30)));
+/// assert!("24:00".parse::<Time>().is_err());
+/// ```
+impl FromStr for Time {
+    type Err = ParseTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':').map(|part| part.parse::<u8>());
+        let hours = parts.next().ok_or(ParseTimeError)?;
+        let minutes = parts.next().ok_or(ParseTimeError)?;
+        let seconds = parts.next().unwrap_or(Ok(0));
+        if parts.next().is_some() {
+            return Err(ParseTimeError);
         }
-        Self {
-            hours_local: hours,
-            minutes_local: minutes,
-            seconds_local: 0,
+        match (hours, minutes, seconds) {
+            (Ok(hours), Ok(minutes), Ok(seconds)) => {
+                Time::try_from_values(hours, minutes, seconds).ok_or(ParseTimeError)
+            }
+            _ => Err(ParseTimeError),
         }
     }
 }
 
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// Returns the duration in whole seconds, reduced to less than a day
+fn seconds_of_day(duration: Duration) -> u32 {
+    (duration.as_secs() % SECONDS_PER_DAY as u64) as u32
+}
+
+/// Move the time forward, wrapping around midnight
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    fn add(self, duration: Duration) -> Time {
+        Time::from_seconds_since_midnight(self.seconds_since_midnight() + seconds_of_day(duration))
+    }
+}
+
+impl AddAssign<Duration> for Time {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+/// Move the time backward, wrapping around midnight
+impl Sub<Duration> for Time {
+    type Output = Time;
+
+    fn sub(self, duration: Duration) -> Time {
+        Time::from_seconds_since_midnight(
+            self.seconds_since_midnight() + SECONDS_PER_DAY - seconds_of_day(duration),
+        )
+    }
+}
+
+impl SubAssign<Duration> for Time {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// Duration from `other` until `self`, wrapping around midnight when `self` is earlier
+impl Sub<Time> for Time {
+    type Output = Duration;
+
+    fn sub(self, other: Time) -> Duration {
+        let seconds = (self.seconds_since_midnight() + SECONDS_PER_DAY
+            - other.seconds_since_midnight())
+            % SECONDS_PER_DAY;
+        Duration::from_secs(seconds as u64)
+    }
+}
+
+/// Step size for rounding a time
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Granularity {
+    Minute,
+    FiveMinutes,
+    TenMinutes,
+    QuarterHour,
+    HalfHour,
+}
+
+impl Granularity {
+    /// Returns the step size in seconds
+    pub const fn seconds(self) -> u32 {
+        match self {
+            Granularity::Minute => 60,
+            Granularity::FiveMinutes => 5 * 60,
+            Granularity::TenMinutes => 10 * 60,
+            Granularity::QuarterHour => 15 * 60,
+            Granularity::HalfHour => 30 * 60,
+        }
+    }
+}
+
+/// Direction for rounding a time
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum RoundingMode {
+    /// Round down to the previous step
+    Floor,
+    /// Round to the closest step, halfway rounds up
+    Nearest,
+    /// Round up to the next step
+    Ceil,
+}
+
 #[cfg(feature = "chrono")]
 use chrono::prelude::*;
-/// Create a time from a chrono DateTime
+
+/// Create a time from the local time of a chrono DateTime in any time zone
+///
+/// # Examples
+/// ```
+/// use chrono::{FixedOffset, TimeZone, Utc};
+/// use watchface::time::Time;
+///
+/// let utc = Utc.with_ymd_and_hms(2020, 9, 3, 19, 23, 2).unwrap();
+/// assert_eq!(Time::from(utc), Time::from_values(19, 23, 2));
+///
+/// let amsterdam = utc.with_timezone(&FixedOffset::east_opt(2 * 60 * 60).unwrap());
+/// assert_eq!(Time::from(amsterdam), Time::from_values(21, 23, 2));
+/// ```
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for Time {
+    fn from(time: DateTime<Tz>) -> Self {
+        time.naive_local().into()
+    }
+}
+
+/// Create a time from a chrono NaiveDateTime
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use watchface::time::Time;
+///
+/// let date_time = NaiveDate::from_ymd_opt(2020, 9, 3)
+///     .unwrap()
+///     .and_hms_opt(7, 8, 9)
+///     .unwrap();
+/// assert_eq!(Time::from(date_time), Time::from_values(7, 8, 9));
+/// ```
 #[cfg(feature = "chrono")]
-impl From<DateTime<Local>> for Time {
-    fn from(time: DateTime<Local>) -> Self {
+impl From<NaiveDateTime> for Time {
+    fn from(time: NaiveDateTime) -> Self {
+        time.time().into()
+    }
+}
+
+/// Create a time from a chrono NaiveTime
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveTime;
+/// use watchface::time::Time;
+///
+/// let time = NaiveTime::from_hms_opt(13, 14, 15).unwrap();
+/// assert_eq!(Time::from(time), Time::from_values(13, 14, 15));
+/// ```
+#[cfg(feature = "chrono")]
+impl From<NaiveTime> for Time {
+    fn from(time: NaiveTime) -> Self {
         Time {
             hours_local: time.hour() as u8,
             minutes_local: time.minute() as u8,
@@ -147,3 +450,128 @@ impl From<DateTime<Local>> for Time {
         }
     }
 }
+
+/// Create a time from a `time` crate Time
+///
+/// # Examples
+/// ```
+/// use watchface::time::Time;
+///
+/// let time = time::Time::from_hms(13, 14, 15).unwrap();
+/// assert_eq!(Time::from(time), Time::from_values(13, 14, 15));
+/// ```
+#[cfg(feature = "time")]
+impl From<::time::Time> for Time {
+    fn from(time: ::time::Time) -> Self {
+        Time {
+            hours_local: time.hour(),
+            minutes_local: time.minute(),
+            seconds_local: time.second(),
+        }
+    }
+}
+
+/// Create a time from a `time` crate PrimitiveDateTime
+#[cfg(feature = "time")]
+impl From<::time::PrimitiveDateTime> for Time {
+    fn from(time: ::time::PrimitiveDateTime) -> Self {
+        time.time().into()
+    }
+}
+
+/// Create a time from the local time of a `time` crate OffsetDateTime
+///
+/// # Examples
+/// ```
+/// use time::{OffsetDateTime, UtcOffset};
+/// use watchface::time::Time;
+///
+/// // 2020-09-03T19:23:02Z
+/// let utc = OffsetDateTime::from_unix_timestamp(1599160982).unwrap();
+/// let amsterdam = utc.to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+/// assert_eq!(Time::from(amsterdam), Time::from_values(21, 23, 2));
+/// ```
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for Time {
+    fn from(time: ::time::OffsetDateTime) -> Self {
+        time.time().into()
+    }
+}
+
+#[cfg(feature = "rtcc")]
+impl Time {
+    /// Read the current time from a real-time clock
+    ///
+    /// The clock is expected to run in local time.
+    ///
+    /// # Examples
+    /// ```
+    /// use rtcc::{DateTimeAccess, NaiveDate, NaiveDateTime};
+    /// use watchface::time::Time;
+    ///
+    /// struct FakeRtc;
+    ///
+    /// impl DateTimeAccess for FakeRtc {
+    ///     type Error = ();
+    ///
+    ///     fn datetime(&mut self) -> Result<NaiveDateTime, ()> {
+    ///         Ok(NaiveDate::from_ymd_opt(2021, 3, 4).unwrap().and_hms_opt(5, 6, 7).unwrap())
+    ///     }
+    ///
+    ///     fn set_datetime(&mut self, _datetime: &NaiveDateTime) -> Result<(), ()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Time::from_rtc(&mut FakeRtc), Ok(Time::from_values(5, 6, 7)));
+    /// ```
+    pub fn from_rtc<R: rtcc::DateTimeAccess>(rtc: &mut R) -> Result<Time, R::Error> {
+        rtc.datetime().map(Time::from)
+    }
+}
+
+/// Serialize a time as a `HH:MM:SS` string in human-readable formats, otherwise as a tuple of
+/// hours, minutes and seconds
+#[cfg(feature = "serde")]
+impl serde::Serialize for Time {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.hours_local, self.minutes_local, self.seconds_local).serialize(serializer)
+        }
+    }
+}
+
+/// Deserialize a time from a `HH:MM[:SS]` string in human-readable formats, otherwise from a tuple
+/// of hours, minutes and seconds
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Time {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected, Visitor};
+
+        struct TimeVisitor;
+
+        impl<'de> Visitor<'de> for TimeVisitor {
+            type Value = Time;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a time in the format HH:MM:SS")
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Time, E> {
+                value
+                    .parse()
+                    .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TimeVisitor)
+        } else {
+            let (hours, minutes, seconds) = serde::Deserialize::deserialize(deserializer)?;
+            Time::try_from_values(hours, minutes, seconds)
+                .ok_or_else(|| D::Error::custom("time value out of range"))
+        }
+    }
+}
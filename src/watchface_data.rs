@@ -17,15 +17,24 @@
 
 use crate::battery::ChargerState;
 use crate::battery::StateOfCharge;
+use crate::date::Date;
 use crate::styled::Styled;
 use crate::time::Time;
 
 /// Representation of watchface data
-#[derive(Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Watchface {
     pub time: Option<Time>,
+    pub date: Option<Date>,
     pub charger: Option<ChargerState>,
     pub battery: Option<StateOfCharge>,
+    /// Number of unread notifications
+    pub notifications: Option<u8>,
+    /// Elapsed time in milliseconds, which drives the animations of the styles
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub animation_time: Option<u32>,
 }
 
 impl Watchface {
@@ -71,6 +80,23 @@ impl WatchfaceBuilder {
         self
     }
 
+    /// Add a date to the watchface data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Local;
+    /// use watchface::Watchface;
+    ///
+    /// let now = Local::now();
+    /// let watchface = Watchface::build().with_time(now).with_date(now).finish();
+    /// ```
+    pub fn with_date<T: Into<Date>>(mut self, date: T) -> Self {
+        self.watchface.date = Some(date.into());
+
+        self
+    }
+
     /// Add a charger state to the watchface data
     pub fn with_charger<T: Into<ChargerState>>(mut self, charger: T) -> Self {
         self.watchface.charger = Some(charger.into());
@@ -85,6 +111,23 @@ impl WatchfaceBuilder {
         self
     }
 
+    /// Add the number of unread notifications to the watchface data
+    pub fn with_notifications(mut self, count: u8) -> Self {
+        self.watchface.notifications = Some(count);
+
+        self
+    }
+
+    /// Add the elapsed time in milliseconds, for example since the start of the application
+    ///
+    /// The styles animate the battery icon while charging, based on this time. The watchface is
+    /// static when the time is not set.
+    pub fn with_animation_time(mut self, elapsed_ms: u32) -> Self {
+        self.watchface.animation_time = Some(elapsed_ms);
+
+        self
+    }
+
     /// Convert the builder to a watchface
     pub fn finish(self) -> Watchface {
         self.watchface
@@ -116,3 +159,63 @@ impl WatchfaceBuilder {
         self.watchface.into_styled(style)
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn watchface() -> Watchface {
+        Watchface::build()
+            .with_time(Time::from_values(10, 9, 30))
+            .with_date(Date::from_values(2021, 3, 4))
+            .with_battery(StateOfCharge::from_level(191))
+            .with_charger(ChargerState::OverTemperature)
+            .with_notifications(3)
+            .finish()
+    }
+
+    #[test]
+    fn json_schema() {
+        let json = concat!(
+            r#"{"time":"10:09:30","date":"2021-03-04","charger":"over_temperature","#,
+            r#""battery":191,"notifications":3}"#
+        );
+        assert_eq!(serde_json::to_string(&watchface()).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<Watchface>(json).unwrap(),
+            watchface()
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Watchface>(r#"{"time":"23:59"}"#).unwrap(),
+            Watchface::build()
+                .with_time(Time::from_values(23, 59, 0))
+                .finish()
+        );
+        assert_eq!(
+            serde_json::from_str::<Watchface>("{}").unwrap(),
+            Watchface::default()
+        );
+        assert!(serde_json::from_str::<Watchface>(r#"{"time":"24:00"}"#).is_err());
+        assert!(serde_json::from_str::<Watchface>(r#"{"date":"2021-02-29"}"#).is_err());
+        assert!(serde_json::from_str::<Watchface>(r#"{"charger":"plugged"}"#).is_err());
+        assert!(serde_json::from_str::<Watchface>(r#"{"notifications":256}"#).is_err());
+    }
+
+    #[test]
+    fn postcard_without_alloc() {
+        let mut buffer = [0; 16];
+        let encoded = postcard::to_slice(&watchface(), &mut buffer).unwrap();
+        assert_eq!(
+            encoded,
+            [1, 10, 9, 30, 1, 229, 15, 3, 4, 1, 4, 1, 191, 1, 3]
+        );
+        assert_eq!(
+            postcard::from_bytes::<Watchface>(encoded).unwrap(),
+            watchface()
+        );
+
+        assert!(postcard::from_bytes::<Time>(&[24, 0, 0]).is_err());
+        assert!(postcard::from_bytes::<Date>(&[229, 15, 2, 29]).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::background::{Background, DrawOnBackground, PlainBackground};
+use crate::phrase::{time_to_words, Language, Word};
+use crate::styled::Styled;
+use crate::time::Time;
+use crate::watchface_data::Watchface;
+use embedded_graphics::mono_font::ascii::{FONT_10X20, FONT_6X10};
+use embedded_graphics::mono_font::MonoTextStyle;
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+
+const GRID_COLUMNS: usize = 11;
+const GRID_ROWS: usize = 10;
+/// First row that is searched for the hour, so that minute words are not used as hours
+const HOUR_START_ROW: usize = 4;
+
+type Grid = [&'static str; GRID_ROWS];
+
+const DUTCH_GRID: Grid = [
+    "HETKISAVIJF",
+    "TIENBTZVOOR",
+    "OVERMEKWART",
+    "HALFSPWOVER",
+    "VOORTHGEENS",
+    "TWEEPVCDRIE",
+    "VIERVIJFZES",
+    "ZEVENONEGEN",
+    "ACHTTIENELF",
+    "TWAALFBFUUR",
+];
+
+const ENGLISH_GRID: Grid = [
+    "ITLISASAMPM",
+    "ACQUARTERDC",
+    "TWENTYFIVEX",
+    "HALFSTENFTO",
+    "PASTERUNINE",
+    "ONESIXTHREE",
+    "FOURFIVETWO",
+    "EIGHTELEVEN",
+    "SEVENTWELVE",
+    "TENSEOCLOCK",
+];
+
+fn grid(language: Language) -> &'static Grid {
+    match language {
+        Language::Dutch => &DUTCH_GRID,
+        Language::English => &ENGLISH_GRID,
+    }
+}
+
+/// Returns the number of letters of `text` if `letters` starts with it
+fn match_word(letters: &[u8], text: &str) -> Option<usize> {
+    let mut length = 0;
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        let c = match c {
+            'é' => 'E',
+            c => c.to_ascii_uppercase(),
+        };
+        if letters.get(length) != Some(&(c as u8)) {
+            return None;
+        }
+        length += 1;
+    }
+    Some(length)
+}
+
+/// Find the first occurrence of a word at or after the letter index `from`
+///
+/// Returns the letter index and the length of the word.
+fn find_word(grid: &Grid, text: &str, from: usize) -> Option<(usize, usize)> {
+    for (row, letters) in grid.iter().enumerate().skip(from / GRID_COLUMNS) {
+        let start = if row == from / GRID_COLUMNS {
+            from % GRID_COLUMNS
+        } else {
+            0
+        };
+        for column in start..GRID_COLUMNS {
+            if let Some(length) = match_word(&letters.as_bytes()[column..], text) {
+                return Some((row * GRID_COLUMNS + column, length));
+            }
+        }
+    }
+    None
+}
+
+/// Returns a bitmask per row of the letters that need to be lit
+fn lit_letters(time: &Time, language: Language) -> [u16; GRID_ROWS] {
+    let grid = grid(language);
+    let mut lit = [0; GRID_ROWS];
+    let mut cursor = 0;
+
+    for word in [Word::It, Word::Is]
+        .iter()
+        .chain(time_to_words(time, language).iter())
+    {
+        let from = match word {
+            Word::Hour(_) => cursor.max(HOUR_START_ROW * GRID_COLUMNS),
+            _ => cursor,
+        };
+        if let Some((index, length)) = find_word(grid, word.text(language), from) {
+            for letter in index..index + length {
+                lit[letter / GRID_COLUMNS] |= 1 << (letter % GRID_COLUMNS);
+            }
+            cursor = index + length;
+        }
+    }
+
+    lit
+}
+
+/// Word clock watchface style
+///
+/// This draws a fixed grid of letters and highlights the words that tell the time, with a
+/// resolution of five minutes.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::Drawable;
+/// use embedded_graphics::mock_display::MockDisplay;
+/// use embedded_graphics::pixelcolor::Rgb888;
+/// use watchface::phrase::Language;
+/// use watchface::time::Time;
+/// use watchface::WordClockWatchfaceStyle;
+/// use watchface::Watchface;
+///
+/// let style = WordClockWatchfaceStyle::default().with_language(Language::English);
+///
+/// let styled_watchface = Watchface::build()
+///      .with_time(Time::from_values(15, 19, 0))
+///      .into_styled(style);
+///
+/// let mut display = MockDisplay::<Rgb888>::new();
+/// display.set_allow_overdraw(true);
+/// styled_watchface.draw(&mut display);
+/// ```
+pub struct WordClockWatchfaceStyle<C> {
+    language: Language,
+    lit_color: C,
+    unlit_color: C,
+}
+
+impl<C> Default for WordClockWatchfaceStyle<C>
+where
+    C: From<Rgb888>,
+{
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            lit_color: Rgb888::WHITE.into(),
+            unlit_color: Rgb888::new(48, 48, 48).into(),
+        }
+    }
+}
+
+impl<C> WordClockWatchfaceStyle<C> {
+    /// Language of the letter grid (default Dutch)
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+
+        self
+    }
+
+    /// Color of the letters that tell the time
+    pub fn with_lit_color(mut self, color: C) -> Self {
+        self.lit_color = color;
+
+        self
+    }
+
+    /// Color of the remaining letters
+    pub fn with_unlit_color(mut self, color: C) -> Self {
+        self.unlit_color = color;
+
+        self
+    }
+}
+
+impl<C> Drawable for Styled<Watchface, WordClockWatchfaceStyle<C>>
+where
+    C: RgbColor,
+{
+    type Color = C;
+
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+    ) -> Result<(), <D as DrawTarget>::Error> {
+        self.draw_on_background(display, &PlainBackground)
+    }
+}
+
+impl<C> DrawOnBackground for Styled<Watchface, WordClockWatchfaceStyle<C>>
+where
+    C: RgbColor,
+{
+    fn draw_on_background<D, B>(&self, display: &mut D, background: &B) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        B: Background<C>,
+    {
+        let display_area = display.bounding_box();
+
+        background.draw_background(&display_area, C::BLACK, display)?;
+
+        let lit = match &self.watchface.time {
+            Some(time) => lit_letters(time, self.style.language),
+            None => [0; GRID_ROWS],
+        };
+
+        let cell_size = Size::new(
+            display_area.size.width / GRID_COLUMNS as u32,
+            display_area.size.height / GRID_ROWS as u32,
+        );
+        let font = if cell_size.width >= 10 && cell_size.height >= 20 {
+            &FONT_10X20
+        } else {
+            &FONT_6X10
+        };
+        let text_style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build();
+
+        for (row, letters) in grid(self.style.language).iter().enumerate() {
+            for column in 0..GRID_COLUMNS {
+                let color = if lit[row] & (1 << column) != 0 {
+                    self.style.lit_color
+                } else {
+                    self.style.unlit_color
+                };
+                let center = display_area.top_left
+                    + Point::new(
+                        (column as u32 * cell_size.width + cell_size.width / 2) as i32,
+                        (row as u32 * cell_size.height + cell_size.height / 2) as i32,
+                    );
+
+                Text::with_text_style(
+                    &letters[column..column + 1],
+                    center,
+                    MonoTextStyle::new(font, color),
+                    text_style,
+                )
+                .draw(display)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Show the lit letters of the grid, replacing the others with a dot
+    fn lit_grid(time: Time, language: Language) -> [[u8; GRID_COLUMNS]; GRID_ROWS] {
+        let lit = lit_letters(&time, language);
+        let mut result = [[b'.'; GRID_COLUMNS]; GRID_ROWS];
+        for (row, letters) in grid(language).iter().enumerate() {
+            for (column, letter) in result[row].iter_mut().enumerate() {
+                if lit[row] & (1 << column) != 0 {
+                    *letter = letters.as_bytes()[column];
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn dutch_ten_to_half_four() {
+        assert_eq!(
+            lit_grid(Time::from_values(15, 19, 0), Language::Dutch),
+            [
+                *b"HET.IS.....",
+                *b"TIEN...VOOR",
+                *b"...........",
+                *b"HALF.......",
+                *b"...........",
+                *b"...........",
+                *b"VIER.......",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+            ]
+        );
+    }
+
+    #[test]
+    fn dutch_ten_o_clock() {
+        assert_eq!(
+            lit_grid(Time::from_values(22, 1, 0), Language::Dutch),
+            [
+                *b"HET.IS.....",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"....TIEN...",
+                *b"........UUR",
+            ]
+        );
+    }
+
+    #[test]
+    fn english_twenty_five_to_one() {
+        assert_eq!(
+            lit_grid(Time::from_values(0, 36, 0), Language::English),
+            [
+                *b"IT.IS......",
+                *b"...........",
+                *b"TWENTYFIVE.",
+                *b".........TO",
+                *b"...........",
+                *b"ONE........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+                *b"...........",
+            ]
+        );
+    }
+}
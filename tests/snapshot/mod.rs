@@ -0,0 +1,126 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Golden-image snapshot testing
+//!
+//! A drawable is rendered into a framebuffer and compared to a reference PNG image in
+//! `tests/references`. When the images differ, the rendered image and a diff image are written to
+//! the target directory. Run the tests with `UPDATE_SNAPSHOTS=1` to (re)create the references.
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use watchface::framebuffer::Framebuffer;
+
+/// Size of the rendered snapshots
+pub const SIZE: Size = Size::new(240, 240);
+
+fn reference_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/references")
+        .join(format!("{}.png", name))
+}
+
+fn output_path(name: &str, suffix: &str) -> PathBuf {
+    let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    fs::create_dir_all(&directory).unwrap();
+    directory.join(format!("{}.{}.png", name, suffix))
+}
+
+fn read_png(path: &Path) -> Option<(Size, Vec<u8>)> {
+    let decoder = png::Decoder::new(File::open(path).ok()?);
+    let mut reader = decoder.read_info().unwrap();
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).unwrap();
+    assert_eq!(info.color_type, png::ColorType::Rgb, "{:?}", path);
+    buffer.truncate(info.buffer_size());
+    Some((Size::new(info.width, info.height), buffer))
+}
+
+fn write_png(path: &Path, size: Size, data: &[u8]) {
+    let file = File::create(path).unwrap();
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size.width, size.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .unwrap()
+        .write_image_data(data)
+        .unwrap();
+}
+
+/// Mark the differing pixels red on a darkened copy of the reference
+fn diff_image(reference: &[u8], actual: &[u8]) -> Vec<u8> {
+    reference
+        .chunks(3)
+        .zip(actual.chunks(3))
+        .flat_map(|(reference, actual)| {
+            if reference == actual {
+                [reference[0] / 4, reference[1] / 4, reference[2] / 4]
+            } else {
+                [255, 0, 0]
+            }
+        })
+        .collect()
+}
+
+/// Render a drawable and compare it to the reference image with the given name
+pub fn assert_snapshot<T>(name: &str, drawable: &T)
+where
+    T: Drawable<Color = Rgb888>,
+{
+    let mut framebuffer = Framebuffer::new(SIZE, Rgb888::BLACK);
+    drawable.draw(&mut framebuffer).unwrap();
+    let actual = framebuffer.to_rgb_bytes();
+
+    let path = reference_path(name);
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        write_png(&path, SIZE, &actual);
+        return;
+    }
+
+    let (size, reference) = read_png(&path).unwrap_or_else(|| {
+        panic!(
+            "Missing reference {:?}, run with UPDATE_SNAPSHOTS=1 to create it",
+            path
+        )
+    });
+
+    if size != SIZE || reference != actual {
+        let actual_path = output_path(name, "actual");
+        write_png(&actual_path, SIZE, &actual);
+
+        if size == SIZE {
+            let diff = diff_image(&reference, &actual);
+            let diff_path = output_path(name, "diff");
+            write_png(&diff_path, SIZE, &diff);
+            let differing = diff.chunks(3).filter(|pixel| pixel == &[255, 0, 0]).count();
+            panic!(
+                "Snapshot {} differs in {} pixels, see {:?} and {:?}",
+                name, differing, actual_path, diff_path
+            );
+        }
+
+        panic!(
+            "Snapshot {} has size {:?} instead of {:?}, see {:?}",
+            name, size, SIZE, actual_path
+        );
+    }
+}
@@ -0,0 +1,182 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+mod snapshot;
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::Drawable;
+use snapshot::assert_snapshot;
+use watchface::battery::{ChargerState, StateOfCharge};
+use watchface::date::Date;
+use watchface::phrase::Language;
+use watchface::time::Time;
+use watchface::{
+    BinaryClockWatchfaceStyle, BinaryMode, SimpleWatchfaceStyle, TextualTimeWatchfaceStyle,
+    Watchface, WordClockWatchfaceStyle,
+};
+
+/// Times to check: midnight, a regular time and a time that rounds up to the next day
+const TIMES: [(&str, Time); 3] = [
+    ("0000", Time::from_values(0, 0, 0)),
+    ("1009", Time::from_values(10, 9, 30)),
+    ("2353", Time::from_values(23, 53, 0)),
+];
+
+/// Battery states to check, drawn at the first time
+const BATTERIES: [(&str, Option<(u8, ChargerState)>); 8] = [
+    ("no_battery", None),
+    ("empty", Some((5, ChargerState::Discharging))),
+    ("charging", Some((50, ChargerState::Charging))),
+    ("full", Some((100, ChargerState::Full))),
+    ("paused", Some((60, ChargerState::Paused))),
+    (
+        "over_temperature",
+        Some((40, ChargerState::OverTemperature)),
+    ),
+    ("fault", Some((30, ChargerState::Fault))),
+    ("unknown", Some((20, ChargerState::Unknown))),
+];
+
+/// Battery state drawn at all times, which differs from the battery states so that no snapshot
+/// of the time matrix duplicates one of the battery matrix
+const TIME_BATTERY: (u8, ChargerState) = (75, ChargerState::Discharging);
+
+fn watchface(time: Time, battery: Option<(u8, ChargerState)>) -> Watchface {
+    let mut builder = Watchface::build()
+        .with_time(time)
+        .with_date(Date::from_values(2021, 3, 4));
+    if let Some((percentage, charger)) = battery {
+        builder = builder
+            .with_battery(StateOfCharge::from_percentage(percentage))
+            .with_charger(charger);
+    }
+    builder.finish()
+}
+
+/// How a style shows the battery state
+#[derive(PartialEq)]
+enum ShowsBattery {
+    No,
+    Text,
+    AnimatedIcon,
+}
+
+/// Check a style at all times and, if it shows the battery, at all battery states
+fn assert_style_snapshots<T, F>(name: &str, style: F, shows_battery: ShowsBattery)
+where
+    T: Drawable<Color = Rgb888>,
+    F: Fn(Watchface) -> T,
+{
+    for (time_name, time) in TIMES.iter() {
+        let styled = style(watchface(*time, Some(TIME_BATTERY)));
+        assert_snapshot(&format!("{}_{}", name, time_name), &styled);
+    }
+
+    if shows_battery != ShowsBattery::No {
+        for (battery_name, battery) in BATTERIES.iter() {
+            let styled = style(watchface(TIMES[0].1, *battery));
+            assert_snapshot(&format!("{}_{}", name, battery_name), &styled);
+        }
+    }
+
+    if shows_battery == ShowsBattery::AnimatedIcon {
+        let mut animated = watchface(TIMES[0].1, Some((50, ChargerState::Charging)));
+        animated.animation_time = Some(600);
+        assert_snapshot(&format!("{}_charging_animated", name), &style(animated));
+    }
+}
+
+#[test]
+fn simple() {
+    assert_style_snapshots(
+        "simple",
+        |watchface| watchface.into_styled(SimpleWatchfaceStyle::default()),
+        ShowsBattery::AnimatedIcon,
+    );
+}
+
+#[test]
+fn simple_anti_aliased() {
+    assert_style_snapshots(
+        "simple_anti_aliased",
+        |watchface| watchface.into_styled(SimpleWatchfaceStyle::default().with_anti_aliasing(true)),
+        ShowsBattery::AnimatedIcon,
+    );
+}
+
+#[test]
+fn textual() {
+    assert_style_snapshots(
+        "textual",
+        |watchface| watchface.into_styled(TextualTimeWatchfaceStyle::default()),
+        ShowsBattery::Text,
+    );
+}
+
+#[test]
+fn word_clock_dutch() {
+    assert_style_snapshots(
+        "word_clock_dutch",
+        |watchface| watchface.into_styled(WordClockWatchfaceStyle::default()),
+        ShowsBattery::No,
+    );
+}
+
+#[test]
+fn word_clock_english() {
+    assert_style_snapshots(
+        "word_clock_english",
+        |watchface| {
+            watchface
+                .into_styled(WordClockWatchfaceStyle::default().with_language(Language::English))
+        },
+        ShowsBattery::No,
+    );
+}
+
+#[test]
+fn binary_clock() {
+    assert_style_snapshots(
+        "binary_clock",
+        |watchface| watchface.into_styled(BinaryClockWatchfaceStyle::default()),
+        ShowsBattery::No,
+    );
+}
+
+#[test]
+fn bcd_clock() {
+    assert_style_snapshots(
+        "bcd_clock",
+        |watchface| {
+            watchface.into_styled(BinaryClockWatchfaceStyle::default().with_mode(BinaryMode::Bcd))
+        },
+        ShowsBattery::No,
+    );
+}
+
+#[cfg(feature = "declarative")]
+#[test]
+fn declarative_digital() {
+    use watchface::declarative::{DeclarativeStyle, Layout};
+
+    let layout = Layout::from_json(include_str!("../examples/layouts/digital.json")).unwrap();
+    assert_style_snapshots(
+        "declarative_digital",
+        |watchface| watchface.into_styled(DeclarativeStyle::new(layout.clone()).unwrap()),
+        ShowsBattery::AnimatedIcon,
+    );
+}
@@ -0,0 +1,119 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use embedded_graphics::pixelcolor::Rgb888;
+use embedded_graphics::prelude::*;
+
+/// A draw target that stores the pixels as RGBA bytes, as used by a HTML canvas `ImageData`
+///
+/// Pixels that are drawn outside of the canvas are ignored.
+pub struct RgbaCanvas {
+    size: Size,
+    data: Vec<u8>,
+}
+
+impl RgbaCanvas {
+    /// Create a transparent canvas, fails when the size doesn't fit in memory
+    pub fn new(size: Size) -> Result<Self, String> {
+        let too_large = || format!("Canvas too large: {}x{}", size.width, size.height);
+        let length = (size.width as usize)
+            .checked_mul(size.height as usize)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .ok_or_else(too_large)?;
+
+        let mut data = Vec::new();
+        data.try_reserve_exact(length).map_err(|_| too_large())?;
+        data.resize(length, 0);
+
+        Ok(Self { size, data })
+    }
+
+    /// Returns the pixels as RGBA bytes, row by row
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl DrawTarget for RgbaCanvas {
+    type Color = Rgb888;
+
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0
+                && point.y >= 0
+                && (point.x as u32) < self.size.width
+                && (point.y as u32) < self.size.height
+            {
+                let index = (point.y as usize * self.size.width as usize + point.x as usize) * 4;
+                self.data[index..index + 4].copy_from_slice(&[
+                    color.r(),
+                    color.g(),
+                    color.b(),
+                    255,
+                ]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl OriginDimensions for RgbaCanvas {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+
+    #[test]
+    fn draw_rgba() {
+        let mut canvas = RgbaCanvas::new(Size::new(2, 2)).unwrap();
+
+        Rectangle::new(Point::new(1, 0), Size::new(2, 1))
+            .into_styled(PrimitiveStyle::with_fill(Rgb888::new(1, 2, 3)))
+            .draw(&mut canvas)
+            .unwrap();
+
+        assert_eq!(
+            canvas.data(),
+            &[0, 0, 0, 0, 1, 2, 3, 255, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn too_large() {
+        assert!(RgbaCanvas::new(Size::new(u32::MAX, u32::MAX)).is_err());
+    }
+
+    #[test]
+    fn allocation_fails() {
+        // Fits in a 64-bit usize, but is more than any allocation can hold
+        assert_eq!(
+            RgbaCanvas::new(Size::new(u32::MAX, 1 << 29)).err(),
+            Some(format!("Canvas too large: {}x{}", u32::MAX, 1 << 29))
+        );
+    }
+}
@@ -0,0 +1,145 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Browser previews of watchface styles
+//!
+//! This crate renders a watchface style into an RGBA buffer, which JavaScript can put on a HTML
+//! canvas. The watchface data is passed as JSON, using the schema of the `serde` feature of the
+//! watchface crate:
+//!
+//! ```json
+//! {
+//!   "time": "10:09:30",
+//!   "date": "2021-03-04",
+//!   "battery": 191,
+//!   "charger": "charging",
+//!   "notifications": 3
+//! }
+//! ```
+//!
+//! All fields are optional. The battery is a level from 0 to 255. The charger is one of
+//! `discharging`, `charging`, `full`, `paused`, `over_temperature`, `fault` or `unknown`. The
+//! notifications are the number of unread notifications, from 0 to 255.
+
+mod canvas;
+
+pub use canvas::RgbaCanvas;
+
+use embedded_graphics::prelude::*;
+use wasm_bindgen::prelude::*;
+use watchface::{NamedStyle, StyleName, Watchface};
+
+/// Parse the JSON watchface data
+pub fn parse_watchface(json: &str) -> Result<Watchface, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+/// Draw a watchface in the named style
+pub fn render_style(
+    canvas: &mut RgbaCanvas,
+    style: &str,
+    watchface: Watchface,
+) -> Result<(), String> {
+    let name: StyleName = style
+        .parse()
+        .map_err(|_| format!("Unknown style: {}", style))?;
+    let result = watchface.into_styled(NamedStyle::new(name)).draw(canvas);
+    result.map_err(|infallible| match infallible {})
+}
+
+/// A canvas that JavaScript can render watchfaces on
+///
+/// ```js
+/// const canvas = new Canvas(240, 240);
+/// canvas.render("word-clock", '{"time": "10:09"}');
+/// const image = new ImageData(new Uint8ClampedArray(canvas.data()), canvas.width(), canvas.height());
+/// context.putImageData(image, 0, 0);
+/// ```
+#[wasm_bindgen]
+pub struct Canvas {
+    canvas: RgbaCanvas,
+}
+
+#[wasm_bindgen]
+impl Canvas {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32) -> Result<Canvas, JsValue> {
+        RgbaCanvas::new(Size::new(width, height))
+            .map(|canvas| Canvas { canvas })
+            .map_err(|message| JsValue::from_str(&message))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.canvas.size().width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.canvas.size().height
+    }
+
+    /// Render a style with the watchface data from JSON
+    pub fn render(&mut self, style: &str, json: &str) -> Result<(), JsValue> {
+        parse_watchface(json)
+            .and_then(|watchface| render_style(&mut self.canvas, style, watchface))
+            .map_err(|message| JsValue::from_str(&message))
+    }
+
+    /// Returns a copy of the RGBA pixels
+    pub fn data(&self) -> Vec<u8> {
+        self.canvas.data().to_vec()
+    }
+}
+
+/// Returns the names of the styles that can be rendered
+#[wasm_bindgen]
+pub fn styles() -> Vec<JsValue> {
+    StyleName::ALL
+        .iter()
+        .map(|style| JsValue::from_str(style.name()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use watchface::battery::{ChargerState, StateOfCharge};
+    use watchface::time::Time;
+
+    #[test]
+    fn parse_json() {
+        let watchface =
+            parse_watchface(r#"{"time": "10:09:30", "battery": 191, "charger": "charging"}"#)
+                .unwrap();
+        assert_eq!(watchface.time, Some(Time::from_values(10, 9, 30)));
+        assert_eq!(watchface.battery, Some(StateOfCharge::from_level(191)));
+        assert_eq!(watchface.charger, Some(ChargerState::Charging));
+
+        assert_eq!(parse_watchface("{}").unwrap(), Watchface::default());
+        assert!(parse_watchface(r#"{"time": "25:00"}"#).is_err());
+        assert!(parse_watchface(r#"{"battery": 256}"#).is_err());
+    }
+
+    #[test]
+    fn render_all_styles() {
+        for style in StyleName::ALL.iter() {
+            let mut canvas = RgbaCanvas::new(Size::new(240, 240)).unwrap();
+            let watchface = parse_watchface(r#"{"time": "10:09"}"#).unwrap();
+            render_style(&mut canvas, style.name(), watchface).unwrap();
+            assert!(canvas.data().chunks(4).any(|pixel| pixel[..3] != [0, 0, 0]));
+        }
+    }
+}
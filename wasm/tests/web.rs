@@ -0,0 +1,42 @@
+/* Copyright (C) 2021 Casper Meijn <casper@meijn.net>
+ * SPDX-License-Identifier: GPL-3.0-or-later
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Tests that run on the wasm32 target, using `wasm-bindgen-test-runner`
+
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::*;
+use watchface_wasm::Canvas;
+
+#[wasm_bindgen_test]
+fn render_in_wasm() {
+    let mut canvas = Canvas::new(240, 240).unwrap();
+    canvas
+        .render("simple", r#"{"time": "10:09", "battery": 128}"#)
+        .unwrap();
+
+    let data = canvas.data();
+    assert_eq!(data.len(), 240 * 240 * 4);
+    assert!(data.chunks(4).all(|pixel| pixel[3] == 255));
+}
+
+#[wasm_bindgen_test]
+fn render_errors_in_wasm() {
+    let mut canvas = Canvas::new(240, 240).unwrap();
+    assert!(canvas.render("unknown", "{}").is_err());
+    assert!(canvas.render("simple", "not json").is_err());
+}